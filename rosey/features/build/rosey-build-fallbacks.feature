@@ -0,0 +1,94 @@
+Feature: Rosey Build Fallbacks
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "rosey.yml" file with the content:
+			"""
+			fallbacks:
+			  pt-BR: [pt]
+			"""
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<h1 data-rosey="title">Hello</h1>
+			<p data-rosey="body">Welcome to the site</p>
+			<span data-rosey="footer">Goodbye</span>
+			<img src="/image.png" />
+			</body>
+			</html>
+			"""
+		And I have a "dist/site/image.png" file with the content:
+			"""
+			Pretend that I'm a png
+			"""
+		And I have a "dist/site/image.pt.png" file with the content:
+			"""
+			Pretend that I'm a portuguese png
+			"""
+		And I have a "rosey/locales/pt-BR.json" file with the content:
+			"""
+			{
+				"title": {
+					"value": "Olá, pessoal"
+				},
+				"body": {
+					"value": ""
+				}
+			}
+			"""
+		And I have a "rosey/locales/pt.json" file with the content:
+			"""
+			{
+				"title": {
+					"value": "Olá"
+				},
+				"body": {
+					"value": "Bem-vindo ao site"
+				}
+			}
+			"""
+
+	Scenario: Rosey build resolves missing keys from fallback locales
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'h1' in "dist/translated_site/pt-BR/index.html" with the attributes:
+			| innerText | Olá, pessoal |
+		And I should see a selector 'p' in "dist/translated_site/pt-BR/index.html" with the attributes:
+			| innerText | Bem-vindo ao site |
+		And I should see a selector 'span' in "dist/translated_site/pt-BR/index.html" with the attributes:
+			| innerText | Goodbye |
+		And I should see a selector 'h1' in "dist/translated_site/pt/index.html" with the attributes:
+			| innerText | Olá |
+
+	Scenario: Rosey build uses translated assets from fallback locales
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'img' in "dist/translated_site/pt-BR/index.html" with the attributes:
+			| src | /image.pt.png |
+		And I should see a selector 'img' in "dist/translated_site/en/index.html" with the attributes:
+			| src | /image.png |
+
+	Scenario: Rosey build resolves translated URLs from fallback locales
+		Given I have a "dist/site/about/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<a href="/about/">About</a>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/pt.urls.json" file with the content:
+			"""
+			{
+				"about/index.html": {
+					"value": "sobre/index.html"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see the file "dist/translated_site/pt-BR/sobre/index.html"
+		And I should see a selector 'a' in "dist/translated_site/pt-BR/sobre/index.html" with the attributes:
+			| href | /pt-BR/sobre/ |
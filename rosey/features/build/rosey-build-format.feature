@@ -0,0 +1,62 @@
+Feature: Rosey Build Locale Formatting
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p class="count" data-rosey-format="number">1234.5</p>
+			<p class="share" data-rosey-format="number:percent" data-rosey-value="0.25">25%</p>
+			<p class="price" data-rosey-format="currency:EUR" data-rosey-value="1234.5">€1,234.50</p>
+			<p class="yen" data-rosey-format="currency:JPY" data-rosey-value="1500">¥1,500</p>
+			<time class="date" data-rosey-format="date:long" datetime="2024-03-05">March 5, 2024</time>
+			<time class="time" data-rosey-format="time:short" datetime="2024-03-05T14:30:00">2:30 PM</time>
+			<p class="broken" data-rosey-format="date:long" data-rosey-value="soon">Soon</p>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/de.json" file with the content:
+			"""
+			{}
+			"""
+		And I have a "rosey/locales/ja.json" file with the content:
+			"""
+			{}
+			"""
+
+	Scenario: Rosey build formats numbers for each locale
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.count' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | 1.234,5 |
+		And I should see a selector '.share' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | 25 % |
+		And I should see a selector '.count' in "dist/translated_site/en/index.html" with the attributes:
+			| innerText | 1234.5 |
+
+	Scenario: Rosey build formats currencies for each locale
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.price' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | 1.234,50 € |
+		And I should see a selector '.yen' in "dist/translated_site/ja/index.html" with the attributes:
+			| innerText | ￥1,500 |
+
+	Scenario: Rosey build formats dates and times for each locale
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.date' in "dist/translated_site/de/index.html" with the attributes:
+			| datetime  | 2024-03-05   |
+			| innerText | 5. März 2024 |
+		And I should see a selector '.date' in "dist/translated_site/ja/index.html" with the attributes:
+			| innerText | 2024年3月5日 |
+		And I should see a selector '.time' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | 14:30 |
+
+	Scenario: Rosey build leaves values it cannot format
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.broken' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | Soon |
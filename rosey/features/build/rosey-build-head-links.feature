@@ -0,0 +1,75 @@
+Feature: Rosey Build Head Links
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/about/index.html" file with the content:
+			"""
+			<html>
+			<head>
+			<link rel="canonical" href="/about/">
+			</head>
+			<body>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{}
+			"""
+		And I have a "rosey/locales/fr.urls.json" file with the content:
+			"""
+			{
+				"about/index.html": {
+					"value": "a-propos/index.html"
+				}
+			}
+			"""
+
+	Scenario: Rosey adds an x-default alternate pointing at the redirect page
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'link[hreflang="x-default"]' in "dist/translated_site/fr/a-propos/index.html" with the attributes:
+			| rel  | alternate |
+			| href | /about/   |
+		And I should see a selector 'link[hreflang="x-default"]' in "dist/translated_site/en/about/index.html" with the attributes:
+			| rel  | alternate |
+			| href | /about/   |
+		And I should see a selector 'link[hreflang="x-default"]' in "dist/translated_site/about/index.html" with the attributes:
+			| rel  | alternate |
+			| href | /about/   |
+
+	Scenario: Rosey points x-default at the default language at the root
+		When I run my program with the flags:
+			| build                      |
+			| --default-language-at-root |
+		Then I should see a selector 'link[hreflang="x-default"]' in "dist/translated_site/fr/a-propos/index.html" with the attributes:
+			| rel  | alternate |
+			| href | /about/   |
+
+	Scenario: Rosey rewrites canonical links to the translated URL
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'link[rel="canonical"]' in "dist/translated_site/fr/a-propos/index.html" with the attributes:
+			| href | /fr/a-propos/ |
+		And I should see a selector 'link[rel="canonical"]' in "dist/translated_site/en/about/index.html" with the attributes:
+			| href | /en/about/ |
+
+	Scenario: Rosey rewrites absolute canonical links on the site
+		Given I have a "dist/site/about/index.html" file with the content:
+			"""
+			<html>
+			<head>
+			<link rel="canonical" href="https://rosey.app/about/">
+			</head>
+			<body>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| build                        |
+			| --base-url https://rosey.app |
+		Then I should see a selector 'link[rel="canonical"]' in "dist/translated_site/fr/a-propos/index.html" with the attributes:
+			| href | https://rosey.app/fr/a-propos/ |
+		And I should see a selector 'link[hreflang="x-default"]' in "dist/translated_site/fr/a-propos/index.html" with the attributes:
+			| href | https://rosey.app/about/ |
@@ -0,0 +1,209 @@
+Feature: Rosey Build Incremental
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p data-rosey="seal">Kiss From A Rose</p>
+			</body>
+			</html>
+			"""
+		And I have a "dist/site/about.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p data-rosey="crazy">Crazy</p>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"seal": "Kiss From A 🐝",
+				"crazy": "🤪"
+			}
+			"""
+
+	Scenario: Rosey incremental build writes a build manifest
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Then I should see the file "rosey/build-cache.json"
+		And I should see '"keys":["seal"]' in "rosey/build-cache.json"
+		And I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+			| data-rosey | seal           |
+			| innerText  | Kiss From A 🐝 |
+
+	Scenario: Rosey incremental build rebuilds pages with changed translations
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Given I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"seal": "Kiss From A 🌹",
+				"crazy": "🤪"
+			}
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Then I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+			| data-rosey | seal           |
+			| innerText  | Kiss From A 🌹 |
+		And I should see a selector 'p' in "dist/translated_site/em/about.html" with the attributes:
+			| data-rosey | crazy |
+			| innerText  | 🤪    |
+
+	Scenario: Rosey incremental build rebuilds pages with changed content
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p data-rosey="crazy">Crazy</p>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Then I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+			| data-rosey | crazy |
+			| innerText  | 🤪    |
+
+	Scenario: Rosey incremental build skips unchanged pages
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Given I have a "dist/translated_site/em/about.html" file with the content:
+			"""
+			<p>untouched</p>
+			"""
+		And I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"seal": "Kiss From A 🌹",
+				"crazy": "🤪"
+			}
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Then I should see a selector 'p' in "dist/translated_site/em/about.html" with the attributes:
+			| innerText | untouched |
+		And I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+			| data-rosey | seal           |
+			| innerText  | Kiss From A 🌹 |
+
+	Scenario: Rosey incremental build rebuilds pages with changed plural forms
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p data-rosey="results" data-rosey-count="3">3 results</p>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"results": {
+					"original": "{count} results",
+					"value": "{count} 🔍",
+					"plurals": { "one": "{count} 🔍", "other": "{count} 🔍🔍" }
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Given I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"results": {
+					"original": "{count} results",
+					"value": "{count} 🔍",
+					"plurals": { "one": "{count} 🔍", "other": "{count} 🔎🔎" }
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Then I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+			| data-rosey | results |
+			| innerText  | 3 🔎🔎  |
+
+	Scenario: Rosey incremental build skips unchanged data files
+		Given I have a "dist/site/search.yml" file with the content:
+			"""
+			placeholder: Search the site
+			"""
+		And I have a "dist/site/search.rosey.yml" file with the content:
+			"""
+			placeholder: rosey:search.placeholder
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Given I have a "dist/translated_site/em/search.yml" file with the content:
+			"""
+			placeholder: untouched
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Then I should see "placeholder: untouched" in "dist/translated_site/em/search.yml"
+		And I should see "placeholder: Search the site" in "dist/translated_site/en/search.yml"
+		And I should not see the file "dist/translated_site/search.yml"
+
+	Scenario: Rosey incremental build skips pages when only translation metadata changes
+		Given I have a "dist/site/search.yml" file with the content:
+			"""
+			placeholder: Search the site
+			"""
+		And I have a "dist/site/search.rosey.yml" file with the content:
+			"""
+			placeholder: rosey:search.placeholder
+			"""
+		And I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"seal": {
+					"value": "Kiss From A 🐝"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Given I have a "dist/translated_site/em/index.html" file with the content:
+			"""
+			<p>untouched</p>
+			"""
+		And I have a "dist/translated_site/em/search.yml" file with the content:
+			"""
+			placeholder: untouched
+			"""
+		And I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"seal": {
+					"original": "Kiss From A Rose",
+					"value": "Kiss From A 🐝",
+					"status": "approved"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build         |
+			| --incremental |
+		Then I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+			| innerText | untouched |
+		And I should see "placeholder: untouched" in "dist/translated_site/em/search.yml"
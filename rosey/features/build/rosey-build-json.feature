@@ -230,4 +230,65 @@ Feature: Rosey Build JSON
       | mushroom.title | Home page title |
     Then I should see "dist/translated_site/consonants/titles.json" containing the values:
       | mushroom.name  | Hm Pg           |
-      | mushroom.title | Home page title |
\ No newline at end of file
+      | mushroom.title | Home page title |
+  Scenario: Rosey builds JSON with keyed namespaces for objects in arrays
+    Given I have a "dist/site/faq.json" file with the content:
+      """
+      {
+        "faqs": [
+          { "id": "shipping", "question": "How long is shipping?" },
+          { "id": "returns", "question": "Can I return items?" }
+        ]
+      }
+      """
+    And I have a "dist/site/faq.rosey.json" file with the content:
+      """
+      {
+        "faqs": [ "rosey-array-ns:id", { "question": "rosey:question" } ]
+      }
+      """
+    And I have a "rosey/locales/consonants.json" file with the content:
+      """
+      {
+        "shipping.question": "Hw lng s shppng?",
+        "returns.question": "Cn  rtrn tms?"
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see "dist/translated_site/consonants/faq.json" containing the values:
+      | faqs.0.id       | shipping         |
+      | faqs.0.question | Hw lng s shppng? |
+      | faqs.1.question | Cn  rtrn tms?    |
+
+  Scenario: Rosey builds JSON from JSONPath rules
+    Given I have a "dist/site/api.json" file with the content:
+      """
+      {
+        "items": [
+          { "slug": "boots", "title": "Walking boots", "price": 10 },
+          { "slug": "hats", "title": "Sun hats", "price": 5 }
+        ]
+      }
+      """
+    And I have a "dist/site/api.rosey.json" file with the content:
+      """
+      {
+        "$.items[*].title": "rosey:{$.items[*].slug}.title",
+        "$.items[*].price": "rosey:price"
+      }
+      """
+    And I have a "rosey/locales/consonants.json" file with the content:
+      """
+      {
+        "boots.title": "Wlkng bts",
+        "hats.title": "Sn hts"
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see "dist/translated_site/consonants/api.json" containing the values:
+      | items.0.title | Wlkng bts |
+      | items.0.price | int:10    |
+      | items.1.title | Sn hts    |
+    And I should see "matched a value that is not a string at $.items[0].price" in stderr
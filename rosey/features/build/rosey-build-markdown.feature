@@ -0,0 +1,92 @@
+Feature: Rosey Build Markdown
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+
+  Scenario: Rosey builds Markdown
+    Given I have a "dist/site/about.md" file with the content:
+      """
+      ---
+      title: About us # rosey:title
+      layout: page
+      ---
+      # <!-- rosey:heading -->Who we are<!-- /rosey -->
+
+      <!-- rosey:intro -->
+      We make things.
+      <!-- /rosey -->
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "title": "À propos: nous",
+        "heading": "Qui sommes-nous"
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see 'title: "À propos: nous"' in "dist/translated_site/fr/about.md"
+    And I should see "# Qui sommes-nous" in "dist/translated_site/fr/about.md"
+    And I should see "We make things." in "dist/translated_site/fr/about.md"
+    And I should not see "rosey" in "dist/translated_site/fr/about.md"
+    And I should see "title: About us" in "dist/translated_site/en/about.md"
+    And I should not see "rosey" in "dist/translated_site/en/about.md"
+    And I should not see "rosey" in "dist/translated_site/about.md"
+
+  Scenario: Rosey builds plain text with namespaces
+    Given I have a "dist/site/llms.txt" file with the content:
+      """
+      <!-- rosey-ns:llms -->
+      <!-- rosey:summary -->A site about mushrooms.<!-- /rosey -->
+      Untagged text
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "llms.summary": "Un site sur les champignons."
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see "Un site sur les champignons." in "dist/translated_site/fr/llms.txt"
+    And I should see "Untagged text" in "dist/translated_site/fr/llms.txt"
+    And I should not see "rosey" in "dist/translated_site/fr/llms.txt"
+    And I should see "A site about mushrooms." in "dist/translated_site/llms.txt"
+
+  Scenario: Rosey copies untagged text files as assets
+    Given I have a "dist/site/robots.txt" file with the content:
+      """
+      User-agent: *
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {}
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see "User-agent: *" in "dist/translated_site/robots.txt"
+    And I should not see the file "dist/translated_site/fr/robots.txt"
+
+  Scenario: Rosey translates Markdown locale overrides for their locale
+    Given I have a "dist/site/docs.md" file with the content:
+      """
+      <!-- rosey:heading -->Documentation<!-- /rosey -->
+      """
+    And I have a "dist/site/fr/docs.md" file with the content:
+      """
+      <!-- rosey:heading -->Documentation<!-- /rosey -->
+      Written for French readers
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "heading": "La documentation"
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see "La documentation" in "dist/translated_site/fr/docs.md"
+    And I should see "Written for French readers" in "dist/translated_site/fr/docs.md"
+    And I should not see "rosey" in "dist/translated_site/fr/docs.md"
+    And I should see "Documentation" in "dist/translated_site/docs.md"
@@ -0,0 +1,75 @@
+Feature: Rosey Build Max Lengths
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<button data-rosey="cta" data-rosey-maxlength="12">Buy now</button>
+			<p data-rosey="body">Everything you need for the garden.</p>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"cta": {
+					"original": "Buy now",
+					"value": "Acheter <b>maintenant</b>"
+				},
+				"body": {
+					"original": "Everything you need for the garden.",
+					"value": "Tout ce dont vous avez besoin pour le jardin."
+				}
+			}
+			"""
+
+	Scenario: Rosey build warns about translations longer than their maximum length
+		When I run my program with the flags:
+			| build |
+		Then I should see 'index.html: fr translation "cta" is 18 characters, longer than its maximum of 12' in stderr
+		And I should not see '"body"' in stderr
+		And I should see a selector 'button' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Acheter maintenant |
+
+	Scenario: Rosey build fails on overlong translations in strict mode
+		When I run my program with the flags:
+			| build    |
+			| --strict |
+		Then I should see "Rosey build failed: 1 translations are longer than their maximum length" in stderr
+
+	Scenario: Rosey build only checks attributes against their own max length
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<img data-rosey="hero" data-rosey-attrs="alt,title" data-rosey-maxlength="12" data-rosey-maxlength-title="10" alt="A garden" title="Garden">
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"hero.alt": "Un jardin au printemps",
+				"hero.title": "Un jardin au printemps"
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see 'fr translation "hero.title" is 22 characters, longer than its maximum of 10' in stderr
+		And I should not see '"hero.alt"' in stderr
+
+	Scenario: Rosey build warns about invalid max lengths
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<button data-rosey="cta" data-rosey-maxlength="abc">Buy now</button>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see 'index.html: ignoring data-rosey-maxlength="abc" on <button>' in stderr
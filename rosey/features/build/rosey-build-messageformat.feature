@@ -0,0 +1,120 @@
+Feature: Rosey Build MessageFormat
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p class="greeting" data-rosey="greeting" data-rosey-args='{"name": "Alice"}'>Hello Alice</p>
+			<p class="items" data-rosey="items" data-rosey-args='{"count": 1200}'>1,200 items</p>
+			<p class="one-item" data-rosey="items" data-rosey-args='{"count": 1}'>1 item</p>
+			<p class="published" data-rosey="published" data-rosey-args='{"date": "2024-03-05"}'>Published March 5, 2024</p>
+			<p class="host" data-rosey="host" data-rosey-args='{"gender": "female", "host": "Alice"}'>Alice invites you</p>
+			<p class="plain" data-rosey="plain">Hello {name}</p>
+			</body>
+			</html>
+			"""
+
+	Scenario: Rosey build interpolates message arguments
+		Given I have a "rosey/locales/de.json" file with the content:
+			"""
+			{
+				"greeting": {
+					"value": "Hallo {name}"
+				},
+				"plain": {
+					"value": "Hallo {name}"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.greeting' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | Hallo Alice |
+		And I should see a selector '.plain' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | Hallo {name} |
+
+	Scenario: Rosey build formats plural, number, and date arguments for the locale
+		Given I have a "rosey/locales/de.json" file with the content:
+			"""
+			{
+				"items": {
+					"value": "{count, plural, one {# Artikel} other {# Artikel insgesamt}}"
+				},
+				"published": {
+					"value": "Veröffentlicht am {date, date, long}"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.items' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | 1.200 Artikel insgesamt |
+		And I should see a selector '.one-item' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | 1 Artikel |
+		And I should see a selector '.published' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | Veröffentlicht am 5. März 2024 |
+
+	Scenario: Rosey build formats date and time arguments with UTC offsets as they were written
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p class="west" data-rosey="starts" data-rosey-args='{"start": "2024-05-01T10:00:00-05:00"}'>Starts May 1, 2024 at 10:00</p>
+			<p class="east" data-rosey="starts" data-rosey-args='{"start": "2024-05-01T10:00:00+02:00"}'>Starts May 1, 2024 at 10:00</p>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/de.json" file with the content:
+			"""
+			{
+				"starts": {
+					"value": "Beginnt am {start, date, long} um {start, time, short}"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.west' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | Beginnt am 1. Mai 2024 um 10:00 |
+		And I should see a selector '.east' in "dist/translated_site/de/index.html" with the attributes:
+			| innerText | Beginnt am 1. Mai 2024 um 10:00 |
+
+	Scenario: Rosey build selects message branches
+		Given I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"host": {
+					"value": "{gender, select, female {{host} vous invite à sa fête} other {{host} vous invite}}"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.host' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Alice vous invite à sa fête |
+
+	Scenario: Rosey build warns about invalid element arguments and outputs the translation as it is
+		Given I have a "dist/site/broken.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p class="greeting" data-rosey="greeting" data-rosey-args='{name: Alice}'>Hello Alice</p>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/de.json" file with the content:
+			"""
+			{
+				"greeting": {
+					"value": "Hallo {name}"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.greeting' in "dist/translated_site/de/broken.html" with the attributes:
+			| innerText | Hallo {name} |
+		And I should see 'Warning: broken.html: ignoring data-rosey-args for "greeting", as it isn't a JSON object' in stderr
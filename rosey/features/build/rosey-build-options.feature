@@ -179,4 +179,18 @@ Feature: Rosey Build Options
       | innerText  | gander |
     And I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
       | data-rosey | goose |
-      | innerText  | 👹    |
\ No newline at end of file
+      | innerText  | 👹    |
+  Scenario: Rosey build rejects an invalid exclusions regex
+    Given I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="seal">Kiss From A Rose</p>
+      </body>
+      </html>
+      """
+    When I run my program with the flags:
+      | build                   |
+      | --exclusions "\.(html$" |
+    Then I should see "Invalid exclusions regex" in stderr
+    And I should not see the file "dist/translated_site/index.html"
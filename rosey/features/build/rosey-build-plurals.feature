@@ -0,0 +1,83 @@
+Feature: Rosey Build Plurals
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p class="one" data-rosey="results" data-rosey-count="1">1 result</p>
+			<p class="few" data-rosey="results" data-rosey-count="3">3 results</p>
+			<p class="many" data-rosey="results" data-rosey-count="5">5 results</p>
+			<p class="decimal" data-rosey="results" data-rosey-count="1.5">1.5 results</p>
+			</body>
+			</html>
+			"""
+
+	Scenario: Rosey build selects plural forms using the locale's plural rules
+		Given I have a "rosey/locales/ru.json" file with the content:
+			"""
+			{
+				"results": {
+					"value": "{count} результатов",
+					"plurals": {
+						"one": "{count} результат",
+						"few": "{count} результата",
+						"many": "{count} результатов",
+						"other": "{count} результата"
+					}
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.one' in "dist/translated_site/ru/index.html" with the attributes:
+			| innerText | 1 результат |
+		And I should see a selector '.few' in "dist/translated_site/ru/index.html" with the attributes:
+			| innerText | 3 результата |
+		And I should see a selector '.many' in "dist/translated_site/ru/index.html" with the attributes:
+			| innerText | 5 результатов |
+		And I should see a selector '.decimal' in "dist/translated_site/ru/index.html" with the attributes:
+			| innerText | 1.5 результата |
+
+	Scenario: Rosey build falls back to the other form and then the value
+		Given I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"results": {
+					"value": "{count} 🔍",
+					"plurals": {
+						"one": "{count} 🔎"
+					}
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.one' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | 1 🔎 |
+		And I should see a selector '.few' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | 3 🔍 |
+
+	Scenario: Rosey build skips empty plural forms
+		Given I have a "rosey/locales/ru.json" file with the content:
+			"""
+			{
+				"results": {
+					"value": "{count} 🔍",
+					"plurals": {
+						"one": "",
+						"few": "",
+						"many": "{count} 🔎",
+						"other": "{count} 🔍🔍"
+					}
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector '.one' in "dist/translated_site/ru/index.html" with the attributes:
+			| innerText | 1 🔍🔍 |
+		And I should see a selector '.many' in "dist/translated_site/ru/index.html" with the attributes:
+			| innerText | 5 🔎 |
@@ -0,0 +1,103 @@
+Feature: Rosey Build PO
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+
+  Scenario: Rosey builds from PO locale files
+    Given I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="seal">Kiss From A Rose</p>
+      <p data-rosey="frog">Ribbit</p>
+      </body>
+      </html>
+      """
+    And I have a "rosey/locales/em.po" file with the content:
+      """
+      msgid ""
+      msgstr ""
+      "Language: em\n"
+
+      #: index.html
+      msgctxt "seal"
+      msgid "Kiss From A Rose"
+      msgstr ""
+      "👄"
+      "🌹"
+
+      #, fuzzy
+      msgctxt "frog"
+      msgid "Ribbit"
+      msgstr "🐸"
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+      | data-rosey | seal |
+      | innerText  | 👄🌹 |
+    And I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+      | data-rosey | frog   |
+      | innerText  | Ribbit |
+
+  Scenario: Rosey builds from PO and JSON locale files together
+    Given I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="seal">Kiss From A Rose</p>
+      </body>
+      </html>
+      """
+    And I have a "rosey/locales/em.po" file with the content:
+      """
+      msgctxt "seal"
+      msgid "Kiss From A Rose"
+      msgstr "👄🌹"
+      """
+    And I have a "rosey/locales/blank.json" file with the content:
+      """
+      {
+        "seal": ""
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see a selector 'p' in "dist/translated_site/em/index.html" with the attributes:
+      | data-rosey | seal |
+      | innerText  | 👄🌹 |
+    And I should see a selector 'p' in "dist/translated_site/blank/index.html" with the attributes:
+      | data-rosey | seal |
+      | innerText  |      |
+
+  Scenario: Rosey builds plural forms from PO locale files
+    Given I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <p class="one" data-rosey="results" data-rosey-count="1">1 result</p>
+      <p class="few" data-rosey="results" data-rosey-count="3">3 results</p>
+      <p class="many" data-rosey="results" data-rosey-count="5">5 results</p>
+      </body>
+      </html>
+      """
+    And I have a "rosey/locales/pl.po" file with the content:
+      """
+      msgctxt "results"
+      msgid "{count} result"
+      msgid_plural "{count} results"
+      msgstr[0] "{count} wynik"
+      msgstr[1] "{count} wyniki"
+      msgstr[2] ""
+      "{count} wyników"
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see a selector '.one' in "dist/translated_site/pl/index.html" with the attributes:
+      | innerText | 1 wynik |
+    And I should see a selector '.few' in "dist/translated_site/pl/index.html" with the attributes:
+      | innerText | 3 wyniki |
+    And I should see a selector '.many' in "dist/translated_site/pl/index.html" with the attributes:
+      | innerText | 5 wyników |
+
@@ -0,0 +1,84 @@
+Feature: Rosey Build Redirect Rules
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<h1>Home</h1>
+			</body>
+			</html>
+			"""
+		And I have a "dist/site/about/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<h1>About</h1>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/fr-ca.json" file with the content:
+			"""
+			{}
+			"""
+		And I have a "rosey/locales/fr-ca.urls.json" file with the content:
+			"""
+			{
+				"about/index.html": {
+					"value": "a-propos/index.html"
+				}
+			}
+			"""
+
+	Scenario: Rosey build writes Netlify redirects and headers
+		Given I have a "dist/site/_redirects" file with the content:
+			"""
+			/old-page/  /about/  301
+			"""
+		When I run my program with the flags:
+			| build               |
+			| --redirects netlify |
+		Then I should see "/old-page/  /about/  301" in "dist/translated_site/_redirects"
+		And I should see "/about/  /fr-ca/a-propos/  302!  Language=fr-ca,fr" in "dist/translated_site/_redirects"
+		And I should see "/about/  /en/about/  302!" in "dist/translated_site/_redirects"
+		And I should see "/  /fr-ca/  302!  Language=fr-ca,fr" in "dist/translated_site/_redirects"
+		And I should see "Vary: Accept-Language, Cookie" in "dist/translated_site/_headers"
+		And I should see the file "dist/translated_site/about/index.html"
+
+	Scenario: Rosey build writes Vercel redirects
+		When I run my program with the flags:
+			| build              |
+			| --redirects vercel |
+		Then I should see "dist/translated_site/vercel.json" containing the values:
+			| redirects.0.source        | /about/           |
+			| redirects.0.has.0.type    | cookie            |
+			| redirects.0.has.0.key     | rosey_language    |
+			| redirects.0.has.0.value   | fr-ca             |
+			| redirects.0.destination   | /fr-ca/a-propos/  |
+			| redirects.1.has.0.type    | header            |
+			| redirects.1.has.0.key     | accept-language   |
+			| redirects.1.destination   | /fr-ca/a-propos/  |
+			| redirects.2.source        | /about/           |
+			| redirects.2.destination   | /en/about/        |
+			| redirects.2.permanent     | bool:false        |
+
+	Scenario: Rosey build writes an nginx map file
+		When I run my program with the flags:
+			| build             |
+			| --redirects nginx |
+		Then I should see "map $http_accept_language $rosey_accept_language {" in "dist/translated_site/rosey-redirects.conf"
+		And I should see '"~*^fr[-_]ca(?![a-z])" "fr-ca";' in "dist/translated_site/rosey-redirects.conf"
+		And I should see '"fr-ca:/about/" "/fr-ca/a-propos/";' in "dist/translated_site/rosey-redirects.conf"
+		And I should see '"en:/about/" "/en/about/";' in "dist/translated_site/rosey-redirects.conf"
+
+	Scenario: Rosey build can skip HTML redirect pages
+		When I run my program with the flags:
+			| build                 |
+			| --redirects netlify   |
+			| --skip-redirect-pages |
+		Then I should not see the file "dist/translated_site/about/index.html"
+		And I should not see the file "dist/translated_site/index.html"
+		And I should see the file "dist/translated_site/en/about/index.html"
+		And I should see the file "dist/translated_site/_redirects"
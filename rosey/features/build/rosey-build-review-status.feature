@@ -0,0 +1,82 @@
+Feature: Rosey Build Review Status
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<h1 data-rosey="title">Hello</h1>
+			<p data-rosey="body">Welcome to the site</p>
+			<span data-rosey="footer">Goodbye</span>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"title": {
+					"original": "Hello",
+					"value": "Bonjour",
+					"status": "approved",
+					"translator": "Camille",
+					"updated_at": "2026-01-14"
+				},
+				"body": {
+					"original": "Welcome to the site",
+					"value": "Bienvenue sur le site",
+					"status": "needs-review",
+					"notes": "Check the tone"
+				},
+				"footer": {
+					"original": "Goodbye",
+					"value": "Au revoir"
+				}
+			}
+			"""
+
+	Scenario: Rosey build uses translations of any review status by default
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'h1' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Bonjour |
+		And I should see a selector 'p' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Bienvenue sur le site |
+		And I should see a selector 'span' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Au revoir |
+
+	Scenario: Rosey build falls back to the original for translations below the minimum status
+		When I run my program with the flags:
+			| build                 |
+			| --min-status approved |
+		Then I should see a selector 'h1' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Bonjour |
+		And I should see a selector 'p' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Welcome to the site |
+		And I should see a selector 'span' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Goodbye |
+
+	Scenario: Rosey build treats translations without a status as drafts
+		When I run my program with the flags:
+			| build                     |
+			| --min-status needs-review |
+		Then I should see a selector 'p' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Bienvenue sur le site |
+		And I should see a selector 'span' in "dist/translated_site/fr/index.html" with the attributes:
+			| innerText | Goodbye |
+
+	Scenario: Rosey build keeps translated URLs with a minimum status
+		Given I have a "rosey/locales/fr.urls.json" file with the content:
+			"""
+			{
+				"index.html": {
+					"original": "index.html",
+					"value": "accueil.html"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| build                 |
+			| --min-status approved |
+		Then I should see the file "dist/translated_site/fr/accueil.html"
@@ -0,0 +1,110 @@
+Feature: Rosey Build Right-To-Left Locales
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<h1 data-rosey="title">Welcome</h1>
+			<p data-rosey="product">Buy the iPhone 15 today</p>
+			<p data-rosey="command" data-rosey-dir="ltr">npm install</p>
+			<p data-rosey="footer">Goodbye</p>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/ar.json" file with the content:
+			"""
+			{
+				"title": {
+					"value": "أهلا بك"
+				},
+				"product": {
+					"value": "اشترِ iPhone 15 اليوم"
+				},
+				"command": {
+					"value": "npm install"
+				}
+			}
+			"""
+		And I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"title": {
+					"value": "Bienvenue"
+				}
+			}
+			"""
+
+	Scenario: Rosey build sets the direction of each locale
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'html' in "dist/translated_site/ar/index.html" with the attributes:
+			| lang | ar  |
+			| dir  | rtl |
+		And I should see a selector 'html' in "dist/translated_site/fr/index.html" with the attributes:
+			| lang | fr |
+		And I should not see '<html dir=' in "dist/translated_site/fr/index.html"
+
+	Scenario: Rosey build keeps the directions of left-to-right pages from a left-to-right source
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html dir="ltr">
+			<body>
+			<h1 data-rosey="title" dir="auto">Welcome</h1>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'html' in "dist/translated_site/fr/index.html" with the attributes:
+			| dir | ltr |
+		And I should see a selector 'h1' in "dist/translated_site/fr/index.html" with the attributes:
+			| dir       | auto      |
+			| innerText | Bienvenue |
+		And I should see a selector 'html' in "dist/translated_site/ar/index.html" with the attributes:
+			| dir | rtl |
+
+	Scenario: Rosey build sets the direction of left-to-right pages from a right-to-left source
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html dir="rtl">
+			<body>
+			<h1 data-rosey="title">مرحبا</h1>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'html' in "dist/translated_site/fr/index.html" with the attributes:
+			| dir | ltr |
+		And I should see a selector 'html' in "dist/translated_site/en/index.html" with the attributes:
+			| dir | rtl |
+
+	Scenario: Rosey build isolates text against the direction of the page
+		When I run my program with the flags:
+			| build |
+		Then I should see "اشترِ <bdi>iPhone 15</bdi> اليوم" in "dist/translated_site/ar/index.html"
+		And I should see a selector 'p[data-rosey="footer"]' in "dist/translated_site/ar/index.html" with the attributes:
+			| dir       | auto    |
+			| innerText | Goodbye |
+		And I should not see 'dir="auto"' in "dist/translated_site/fr/index.html"
+
+	Scenario: Rosey build uses data-rosey-dir for the direction of an element
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'p[data-rosey="command"]' in "dist/translated_site/ar/index.html" with the attributes:
+			| dir       | ltr         |
+			| innerText | npm install |
+
+	Scenario: Rosey build reads locale directions from the config
+		Given I have a "rosey.yml" file with the content:
+			"""
+			directions:
+			  fr: rtl
+			"""
+		When I run my program with the flags:
+			| build |
+		Then I should see a selector 'html' in "dist/translated_site/fr/index.html" with the attributes:
+			| dir | rtl |
@@ -0,0 +1,89 @@
+Feature: Rosey Build Sitemap
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE   | dist/site            |
+			| ROSEY_DEST     | dist/translated_site |
+			| ROSEY_BASE_URL | https://example.com  |
+		And I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<h1>Home</h1>
+			</body>
+			</html>
+			"""
+		And I have a "dist/site/about/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<h1>About</h1>
+			</body>
+			</html>
+			"""
+		And I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{}
+			"""
+		And I have a "rosey/locales/fr.urls.json" file with the content:
+			"""
+			{
+				"about/index.html": {
+					"value": "a-propos/index.html"
+				}
+			}
+			"""
+
+	Scenario: Rosey build does not write a sitemap by default
+		When I run my program with the flags:
+			| build |
+		Then I should not see the file "dist/translated_site/sitemap.xml"
+
+	Scenario: Rosey build writes a sitemap with alternate links for every locale
+		When I run my program with the flags:
+			| build     |
+			| --sitemap |
+		Then I should see '<loc>https://example.com/fr/a-propos/</loc>' in "dist/translated_site/sitemap.xml"
+		And I should see '<loc>https://example.com/en/about/</loc>' in "dist/translated_site/sitemap.xml"
+		And I should see '<xhtml:link rel="alternate" hreflang="fr" href="https://example.com/fr/a-propos/"/>' in "dist/translated_site/sitemap.xml"
+		And I should see '<xhtml:link rel="alternate" hreflang="en" href="https://example.com/en/about/"/>' in "dist/translated_site/sitemap.xml"
+		And I should see '<xhtml:link rel="alternate" hreflang="x-default" href="https://example.com/about/"/>' in "dist/translated_site/sitemap.xml"
+		And I should see '<loc>https://example.com/fr/</loc>' in "dist/translated_site/sitemap.xml"
+
+	Scenario: Rosey build points x-default at the default language when it is at the root
+		When I run my program with the flags:
+			| build                      |
+			| --sitemap                  |
+			| --default-language-at-root |
+		Then I should see '<loc>https://example.com/about/</loc>' in "dist/translated_site/sitemap.xml"
+		And I should see '<xhtml:link rel="alternate" hreflang="x-default" href="https://example.com/about/"/>' in "dist/translated_site/sitemap.xml"
+
+	Scenario: Rosey build rewrites an existing sitemap from the source
+		Given I have a "dist/site/sitemap.xml" file with the content:
+			"""
+			<?xml version="1.0" encoding="UTF-8"?>
+			<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+			  <url>
+			    <loc>https://example.com/about/</loc>
+			    <lastmod>2024-03-05</lastmod>
+			  </url>
+			  <url>
+			    <loc>https://elsewhere.com/</loc>
+			  </url>
+			</urlset>
+			"""
+		When I run my program with the flags:
+			| build     |
+			| --sitemap |
+		Then I should see '<loc>https://example.com/fr/a-propos/</loc>' in "dist/translated_site/sitemap.xml"
+		And I should see '<lastmod>2024-03-05</lastmod>' in "dist/translated_site/sitemap.xml"
+		And I should see '<loc>https://elsewhere.com/</loc>' in "dist/translated_site/sitemap.xml"
+		And I should not see '<loc>https://example.com/fr/</loc>' in "dist/translated_site/sitemap.xml"
+
+	Scenario: Rosey build requires a base URL to write a sitemap
+		Given I have the environment variables:
+			| ROSEY_BASE_URL |  |
+		When I run my program with the flags:
+			| build     |
+			| --sitemap |
+		Then I should see "Writing a sitemap requires a base URL" in stderr
+		And I should not see the file "dist/translated_site/sitemap.xml"
@@ -0,0 +1,62 @@
+Feature: Rosey Build Word Wrap Modes
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+    And I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="p" style="color: red">Hello World</p>
+      <div data-rosey="div">Hello <b>World</b></div>
+      </body>
+      </html>
+      """
+    And I have a "rosey/locales/km.json" file with the content:
+      """
+      {
+        "p": {
+          "original": "Hello World",
+          "value": "ភាសាខ្មែរគឺជាភាសា"
+        },
+        "div": {
+          "original": "Hello <b>World</b>",
+          "value": "ភាសាខ្មែរគឺជា <b>ភាសា</b>"
+        }
+      }
+      """
+
+  Scenario: Rosey build can mark word breaks with wbr elements
+    When I run my program with the flags:
+      | build              |
+      | --wrap "km"        |
+      | --wrap-mode "wbr"  |
+    Then I should see "ភាសាខ្មែរ<wbr>គឺជា<wbr>ភាសា</p>" in "dist/translated_site/km/index.html"
+    And I should see "ភាសាខ្មែរ<wbr>គឺជា <b>ភាសា</b>" in "dist/translated_site/km/index.html"
+    And I should see a selector 'p' in "dist/translated_site/km/index.html" with the attributes:
+      | style | color: red; word-break: keep-all; |
+    And I should see a selector 'div' in "dist/translated_site/km/index.html" with the attributes:
+      | style | word-break: keep-all; |
+    And I should not see a selector 'p > span' in "dist/translated_site/km/index.html"
+
+  Scenario: Rosey build can mark word breaks with zero-width spaces
+    When I run my program with the flags:
+      | build              |
+      | --wrap "km"        |
+      | --wrap-mode "zwsp" |
+    Then I should see "ភាសាខ្មែរ​គឺជា​ភាសា</p>" in "dist/translated_site/km/index.html"
+    And I should see a selector 'p' in "dist/translated_site/km/index.html" with the attributes:
+      | style | color: red; word-break: keep-all; |
+
+  Scenario: Rosey build adds the wrap class to the container when marking word breaks
+    When I run my program with the flags:
+      | build                |
+      | --wrap "km"          |
+      | --wrap-mode "wbr"    |
+      | --wrap-class "words" |
+    Then I should see a selector 'p' in "dist/translated_site/km/index.html" with the attributes:
+      | class | words      |
+      | style | color: red |
+    And I should see a selector 'p' in "dist/translated_site/en/index.html" with the attributes:
+      | style | color: red |
+    And I should not see a selector 'p.words' in "dist/translated_site/en/index.html"
@@ -0,0 +1,94 @@
+Feature: Rosey Build Word Wrap Scripts
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+    And I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="p">Hello World</p>
+      </body>
+      </html>
+      """
+
+  Scenario: Rosey build keeps Korean words whole when wrapping
+    Given I have a "rosey/locales/ko.json" file with the content:
+      """
+      {
+        "p": {
+          "original": "Hello World",
+          "value": "한국어는 아름다운 언어입니다"
+        }
+      }
+      """
+    When I run my program with the flags:
+      | build       |
+      | --wrap "ko" |
+    Then I should see a selector 'p > span:nth-of-type(1)' in "dist/translated_site/ko/index.html" with the attributes:
+      | style     | white-space: nowrap; |
+      | innerText | 한국어는             |
+    Then I should see a selector 'p > span:nth-of-type(2)' in "dist/translated_site/ko/index.html" with the attributes:
+      | innerText | 아름다운 |
+    Then I should see a selector 'p > span:nth-of-type(3)' in "dist/translated_site/ko/index.html" with the attributes:
+      | innerText | 언어입니다 |
+
+  Scenario: Rosey build wraps Lao and Burmese by syllable
+    Given I have a "rosey/locales/lo.json" file with the content:
+      """
+      {
+        "p": {
+          "original": "Hello World",
+          "value": "ພາສາລາວ"
+        }
+      }
+      """
+    And I have a "rosey/locales/my.json" file with the content:
+      """
+      {
+        "p": {
+          "original": "Hello World",
+          "value": "မြန်မာဘာသာ"
+        }
+      }
+      """
+    When I run my program with the flags:
+      | build       |
+      | --wrap "lo" |
+      | --wrap "my" |
+    Then I should see a selector 'p > span:nth-of-type(1)' in "dist/translated_site/lo/index.html" with the attributes:
+      | innerText | ພາ |
+    Then I should see a selector 'p > span:nth-of-type(3)' in "dist/translated_site/lo/index.html" with the attributes:
+      | innerText | ລາວ |
+    Then I should see a selector 'p > span:nth-of-type(1)' in "dist/translated_site/my/index.html" with the attributes:
+      | innerText | မြန် |
+    Then I should see a selector 'p > span:nth-of-type(4)' in "dist/translated_site/my/index.html" with the attributes:
+      | innerText | သာ |
+
+  Scenario: Rosey build can select languages to wrap by script
+    Given I have a "rosey/locales/km.json" file with the content:
+      """
+      {
+        "p": {
+          "original": "Hello World",
+          "value": "ភាសាខ្មែរគឺជាភាសា"
+        }
+      }
+      """
+    And I have a "rosey/locales/lo.json" file with the content:
+      """
+      {
+        "p": {
+          "original": "Hello World",
+          "value": "ພາສາລາວ"
+        }
+      }
+      """
+    When I run my program with the flags:
+      | build         |
+      | --wrap "Khmr" |
+    Then I should see a selector 'p > span:nth-of-type(1)' in "dist/translated_site/km/index.html" with the attributes:
+      | innerText | ភាសាខ្មែរ |
+    Then I should see a selector 'p > span:nth-of-type(2)' in "dist/translated_site/km/index.html" with the attributes:
+      | innerText | គឺជា |
+    Then I should not see a selector 'p > span' in "dist/translated_site/lo/index.html"
@@ -0,0 +1,107 @@
+Feature: Rosey Build YAML and TOML
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+
+  Scenario: Rosey builds YAML
+    Given I have a "dist/site/search.yml" file with the content:
+      """
+      placeholder: Search the site
+      results:
+        - name: Docs
+          hint: Read the docs
+      """
+    And I have a "dist/site/search.rosey.yml" file with the content:
+      """
+      placeholder: rosey:search.placeholder
+      results:
+        - name: rosey-ns|rosey:name
+          hint: rosey:hint
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "search.placeholder": "Rechercher",
+        "docs.hint": "Lire la doc"
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see "placeholder: Rechercher" in "dist/translated_site/fr/search.yml"
+    And I should see "hint: Lire la doc" in "dist/translated_site/fr/search.yml"
+    And I should see "name: Docs" in "dist/translated_site/fr/search.yml"
+    And I should see "placeholder: Search the site" in "dist/translated_site/en/search.yml"
+    And I should not see the file "dist/translated_site/search.rosey.yml"
+
+  Scenario: Rosey builds YAML and keeps comments, quoting, and key order
+    Given I have a "dist/site/cms.yml" file with the content:
+      """
+      # CMS config
+      title: My site # the title
+      tagline: 'Built with care'
+      zeta: Last key
+      alpha: First key
+      intro: |
+        Welcome to
+        my site
+      """
+    And I have a "dist/site/cms.rosey.yml" file with the content:
+      """
+      title: rosey:title
+      tagline: rosey:tagline
+      zeta: rosey:zeta
+      alpha: rosey:alpha
+      intro: rosey:intro
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "title": "Mon site",
+        "tagline": "Fait avec l'amour",
+        "zeta": "Note: dernière clé",
+        "intro": "Bienvenue sur\nmon site\n"
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see '# CMS config' in "dist/translated_site/fr/cms.yml"
+    And I should see 'title: Mon site # the title' in "dist/translated_site/fr/cms.yml"
+    And I should see "tagline: 'Fait avec l''amour'" in "dist/translated_site/fr/cms.yml"
+    And I should see 'zeta: "Note: dernière clé"' in "dist/translated_site/fr/cms.yml"
+    And I should see 'alpha: First key' in "dist/translated_site/fr/cms.yml"
+    And I should see '  mon site' in "dist/translated_site/fr/cms.yml"
+    And I should see 'title: My site # the title' in "dist/translated_site/en/cms.yml"
+
+  Scenario: Rosey builds TOML and keeps comments
+    Given I have a "dist/site/cms.toml" file with the content:
+      """
+      # CMS config
+      title = "My site" # the title
+
+      [[collections]]
+      label = "Posts"
+      path = "posts"
+      """
+    And I have a "dist/site/cms.rosey.toml" file with the content:
+      """
+      title = "rosey:cms.title"
+
+      [[collections]]
+      label = "rosey-ns|rosey:label"
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "cms.title": "Mon site",
+        "posts.label": "Articles"
+      }
+      """
+    When I run my program with the flags:
+      | build |
+    Then I should see '# CMS config' in "dist/translated_site/fr/cms.toml"
+    And I should see 'title = "Mon site" # the title' in "dist/translated_site/fr/cms.toml"
+    And I should see 'label = "Articles"' in "dist/translated_site/fr/cms.toml"
+    And I should see 'path = "posts"' in "dist/translated_site/fr/cms.toml"
+    And I should see 'title = "My site"' in "dist/translated_site/en/cms.toml"
+    And I should not see the file "dist/translated_site/cms.rosey.toml"
@@ -0,0 +1,66 @@
+Feature: Rosey check placeholders
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		Given I have a "rosey/base.json" file with the content:
+			"""
+			{
+				"version": 2,
+				"keys": {
+					"greeting": {
+						"original": "Hello {name}",
+						"pages": {
+							"index.html": 1
+						},
+						"total": 1
+					},
+					"items": {
+						"original": "{count, plural, one {# item} other {# items}}",
+						"pages": {
+							"index.html": 1
+						},
+						"total": 1
+					}
+				}
+			}
+			"""
+
+	Scenario: Rosey check flags translations with mismatched placeholders
+		Given I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"greeting": {
+					"original": "Hello {name}",
+					"value": "Bonjour {nom}"
+				},
+				"items": {
+					"original": "{count, plural, one {# item} other {# items}}",
+					"value": "{count, plural, one {# article} other {# articles}}"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| check                |
+			| --fail-on mismatched |
+		Then I should see "rosey/checks.json" containing the values:
+			| fr.states.mismatched | int:1      |
+			| fr.states.current    | int:1      |
+			| fr.keys.greeting     | mismatched |
+			| fr.keys.items        | current    |
+		And I should see "fr: 1 mismatched translations" in stderr
+
+	Scenario: Rosey check flags translations that are not valid messages
+		Given I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"greeting": {
+					"original": "Hello {name}",
+					"value": "Bonjour {name"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| check |
+		Then I should see "rosey/checks.json" containing the values:
+			| fr.keys.greeting | mismatched |
@@ -0,0 +1,73 @@
+Feature: Rosey check reports
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+		Given I have a "rosey/base.json" file with the content:
+			"""
+			{
+				"version": 2,
+				"keys": {
+					"seal": {
+						"original": "Kiss From A Rose",
+						"pages": {
+							"index.html": 1
+						},
+						"total": 1
+					},
+					"crazy": {
+						"original": "Crazy",
+						"pages": {
+							"index.html": 1
+						},
+						"total": 1
+					}
+				}
+			}
+			"""
+		And I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"seal": {
+					"original": "Kiss From A Rose",
+					"value": "Kiss From A 🐝"
+				}
+			}
+			"""
+
+	Scenario: Rosey check prints a summary table
+		When I run my program with the flags:
+			| check |
+		Then I should see "Rosey check summary:" in stdout
+		And I should see "em" in stdout
+		And I should see "rosey/checks.json" containing the values:
+			| em.states.missing | int:1 |
+
+	Scenario: Rosey check can output a JUnit report
+		When I run my program with the flags:
+			| check          |
+			| --format junit |
+		Then I should not see the file "rosey/checks.json"
+		And I should see '<testsuite name="em" tests="2" failures="1" skipped="0">' in "rosey/checks.xml"
+		And I should see '<testcase classname="rosey.em" name="seal"/>' in "rosey/checks.xml"
+		And I should see '<failure message="Translation is missing"/>' in "rosey/checks.xml"
+
+	Scenario: Rosey check can output a Markdown report
+		When I run my program with the flags:
+			| check             |
+			| --format markdown |
+		Then I should see "| em | 1 | 0 | 0 | 1 | 0 |" in "rosey/checks.md"
+		And I should see "| `crazy` | missing |" in "rosey/checks.md"
+
+	Scenario: Rosey check fails on configured states
+		When I run my program with the flags:
+			| check                      |
+			| --fail-on outdated,missing |
+		Then I should see "Rosey check failed:" in stderr
+		And I should see "em: 1 missing translations" in stderr
+
+	Scenario: Rosey check passes when no configured states are found
+		When I run my program with the flags:
+			| check            |
+			| --fail-on unused |
+		Then I should not see "Rosey check failed" in stderr
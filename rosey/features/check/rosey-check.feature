@@ -19,7 +19,8 @@ Feature: Rosey check v2
 			"""
 			{
 				"seal": {
-					"original": "Kiss From A Rose"
+					"original": "Kiss From A Rose",
+					"value": "Kiss From A 🐝"
 				}
 			}
 			"""
@@ -51,7 +52,8 @@ Feature: Rosey check v2
 			"""
 			{
 				"seal": {
-					"original": "Kiss Fromage Rose"
+					"original": "Kiss Fromage Rose",
+					"value": "Kiss From A 🐝"
 				}
 			}
 			"""
@@ -160,4 +162,154 @@ Feature: Rosey check v2
 		Then I should see "rosey/checks.json" containing the values:
 			| em.current   | bool:false |
 			| em.baseTotal | int:4      |
-			| em.total     | int:2      |
\ No newline at end of file
+			| em.total     | int:2      |
+	Scenario: Rosey check counts review statuses
+		Given I have a "rosey/base.json" file with the content:
+			"""
+			{
+				"version": 2,
+				"keys": {
+					"1": {
+						"original": "1"
+					},
+					"2": {
+						"original": "2"
+					},
+					"3": {
+						"original": "3"
+					}
+				}
+			}
+			"""
+		And I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"1": {
+					"original": "1",
+					"value": "one",
+					"status": "approved"
+				},
+				"2": {
+					"original": "2",
+					"value": "two",
+					"status": "approved"
+				},
+				"3": {
+					"original": "3",
+					"value": "three",
+					"status": "draft"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| check |
+		Then I should see "rosey/checks.json" containing the values:
+			| em.statuses.approved | int:2 |
+			| em.statuses.draft    | int:1 |
+		And I should see "em      2 approved, 1 draft" in stdout
+
+	Scenario: Rosey check reports translations longer than their maximum length
+		Given I have a "rosey/base.json" file with the content:
+			"""
+			{
+				"version": 2,
+				"keys": {
+					"cta": {
+						"original": "Buy now",
+						"maxlength": 12
+					},
+					"body": {
+						"original": "Everything you need for the garden."
+					}
+				}
+			}
+			"""
+		And I have a "rosey/locales/fr.json" file with the content:
+			"""
+			{
+				"cta": {
+					"original": "Buy now",
+					"value": "Acheter <b>maintenant</b>"
+				},
+				"body": {
+					"original": "Everything you need for the garden.",
+					"value": "Tout ce dont vous avez besoin pour le jardin."
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| check                |
+			| --fail-on overlong   |
+		Then I should see "rosey/checks.json" containing the values:
+			| fr.states.overlong | int:1    |
+			| fr.keys.cta        | overlong |
+			| fr.keys.body       | current  |
+		And I should see "fr: 1 overlong translations" in stderr
+
+	Scenario: Rosey check rejects unknown review statuses
+		Given I have a "rosey/base.json" file with the content:
+			"""
+			{
+				"version": 2,
+				"keys": {
+					"seal": {
+						"original": "Kiss From A Rose"
+					}
+				}
+			}
+			"""
+		And I have a "rosey/locales/em.json" file with the content:
+			"""
+			{
+				"seal": {
+					"original": "Kiss From A Rose",
+					"value": "Kiss From A 🐝",
+					"status": "aproved"
+				}
+			}
+			"""
+		When I run my program with the flags:
+			| check |
+		Then I should see "Unknown review status 'aproved' for key 'seal'" in stderr
+
+	Scenario: Rosey check reads PO locale files
+		Given I have a "rosey/base.json" file with the content:
+			"""
+			{
+				"version": 2,
+				"keys": {
+					"seal": {
+						"original": "Kiss From A Rose"
+					},
+					"frog": {
+						"original": "Ribbit"
+					},
+					"crow": {
+						"original": "Caw"
+					}
+				}
+			}
+			"""
+		And I have a "rosey/locales/em.po" file with the content:
+			"""
+			msgid ""
+			msgstr ""
+			"Language: em\n"
+
+			msgctxt "seal"
+			msgid "Kiss From A Rose"
+			msgstr "Kiss From A 🐝"
+
+			msgctxt "frog"
+			msgid "Croak"
+			msgstr "🐸"
+			"""
+		When I run my program with the flags:
+			| check |
+		Then I should see "rosey/checks.json" containing the values:
+			| em.keys.seal       | current  |
+			| em.keys.frog       | outdated |
+			| em.keys.crow       | missing  |
+			| em.states.current  | int:1    |
+			| em.states.outdated | int:1    |
+			| em.states.missing  | int:1    |
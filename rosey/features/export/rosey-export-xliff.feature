@@ -0,0 +1,120 @@
+Feature: Rosey Export XLIFF
+
+  Scenario: Rosey exports XLIFF 1.2 files for each locale
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "seal": {
+            "original": "Kiss From A <b>Rose</b>",
+            "pages": {
+              "index.html": 1
+            },
+            "total": 1
+          },
+          "frog": {
+            "original": "Ribbit",
+            "pages": {
+              "pond.html": 1
+            },
+            "total": 1
+          }
+        }
+      }
+      """
+    And I have a "rosey/locales/em.json" file with the content:
+      """
+      {
+        "seal": {
+          "original": "Kiss From A <b>Rose</b>",
+          "value": "👄🌹"
+        }
+      }
+      """
+    When I run my program with the flags:
+      | export         |
+      | --format xliff  |
+    Then I should see '<xliff version="1.2"' in "rosey/exports/em.xlf"
+    And I should see 'source-language="en" target-language="em"' in "rosey/exports/em.xlf"
+    And I should see '<trans-unit id="seal" resname="seal">' in "rosey/exports/em.xlf"
+    And I should see '<source>Kiss From A &lt;b&gt;Rose&lt;/b&gt;</source>' in "rosey/exports/em.xlf"
+    And I should see '<target state="translated">👄🌹</target>' in "rosey/exports/em.xlf"
+    And I should see '<note from="rosey">index.html</note>' in "rosey/exports/em.xlf"
+    And I should see '<trans-unit id="frog" resname="frog">' in "rosey/exports/em.xlf"
+
+  Scenario: Rosey exports XLIFF 2.0 files for each locale
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "seal": {
+            "original": "Kiss From A Rose",
+            "pages": {
+              "index.html": 1
+            },
+            "total": 1
+          }
+        }
+      }
+      """
+    And I have a "rosey/locales/em.json" file with the content:
+      """
+      {
+        "seal": {
+          "original": "Kiss From A Rose",
+          "value": "👄🌹"
+        }
+      }
+      """
+    When I run my program with the flags:
+      | export                |
+      | --xliff-version "2.0" |
+    Then I should see 'version="2.0" srcLang="en" trgLang="em"' in "rosey/exports/em.xlf"
+    And I should see '<unit id="seal" name="seal">' in "rosey/exports/em.xlf"
+    And I should see '<note category="location">index.html</note>' in "rosey/exports/em.xlf"
+    And I should see '<segment state="translated">' in "rosey/exports/em.xlf"
+    And I should see '<target>👄🌹</target>' in "rosey/exports/em.xlf"
+
+  Scenario: Rosey exports outdated V1 translations for review
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "seal": "Kiss From A Rose"
+      }
+      """
+    And I have a "rosey/locales/em.json" file with the content:
+      """
+      {
+        "seal": "👄🌹"
+      }
+      """
+    When I run my program with the flags:
+      | export |
+    Then I should see '<source>Kiss From A Rose</source>' in "rosey/exports/em.xlf"
+    And I should see '<target state="translated">👄🌹</target>' in "rosey/exports/em.xlf"
+
+  Scenario: Rosey exports XLIFF files from PO locale files
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "seal": {
+            "original": "Kiss From A Rose"
+          }
+        }
+      }
+      """
+    And I have a "rosey/locales/em.po" file with the content:
+      """
+      msgctxt "seal"
+      msgid "Kiss From A Rose"
+      msgstr "👄🌹"
+      """
+    When I run my program with the flags:
+      | export         |
+      | --format xliff  |
+    Then I should see '<trans-unit id="seal" resname="seal">' in "rosey/exports/em.xlf"
+    And I should see '<target state="translated">👄🌹</target>' in "rosey/exports/em.xlf"
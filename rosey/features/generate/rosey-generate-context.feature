@@ -0,0 +1,51 @@
+Feature: Rosey Generate Context
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+    And I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <main>
+      <h1>Shop</h1>
+      <p>Everything you need for the garden.</p>
+      <p data-rosey="cta" data-rosey-note="Button label, keep it short">Buy now</p>
+      <p>Free shipping on all orders</p>
+      </main>
+      <nav id="menu"><a href="/" data-rosey="home" data-rosey-attrs="title" title="Go home">Home</a></nav>
+      </body>
+      </html>
+      """
+
+  Scenario: Rosey generate doesn't record context by default
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.cta.original | Buy now |
+    And I should not see "context" in "rosey/base.json"
+
+  Scenario: Rosey generate records the context of each key
+    When I run my program with the flags:
+      | generate  |
+      | --context |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.cta.context.element          | p                                      |
+      | keys.cta.context.selector         | html > body > main > p:nth-of-type(2)  |
+      | keys.cta.context.before           | Everything you need for the garden.    |
+      | keys.cta.context.after            | Free shipping on all orders            |
+      | keys.cta.context.note             | Button label, keep it short            |
+      | keys.home.context.selector        | nav#menu > a                           |
+      | keys.home\.title.context.selector | nav#menu > a                           |
+
+  Scenario: Rosey export includes notes from the context
+    Given I have a "rosey/locales/fr.json" file with the content:
+      """
+      {}
+      """
+    When I run my program with the flags:
+      | generate  |
+      | --context |
+    And I run my program with the flags:
+      | export |
+    Then I should see '<note from="developer">Button label, keep it short</note>' in "rosey/exports/fr.xlf"
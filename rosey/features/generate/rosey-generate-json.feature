@@ -228,3 +228,95 @@ Feature: Rosey Generate JSON
       | keys.mark\.green\.value.original  | green  |
       | keys.mark\.square\.value.original | square |
       | keys.mark\.top\.value.original    | top    |
+
+  Scenario: Rosey generates keyed namespaces for objects in arrays
+    Given I have a "dist/site/faq.json" file with the content:
+      """
+      {
+        "faqs": [
+          { "id": "Shipping", "question": "How long is shipping?" },
+          { "id": "Returns", "question": "Can I return items?" },
+          { "id": 2, "question": "Do you ship abroad?" },
+          { "question": "Where are you based?" }
+        ],
+        "steps": [
+          { "text": "Sign up" },
+          { "text": "Log in" }
+        ]
+      }
+      """
+    And I have a "dist/site/faq.rosey.json" file with the content:
+      """
+      {
+        "faqs": [ "rosey-array-ns:id", { "question": "rosey:question" } ],
+        "steps": [ "rosey-array-ns:$index", { "text": "rosey:text" } ]
+      }
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.shipping\.question.original | How long is shipping? |
+      | keys.returns\.question.original  | Can I return items?   |
+      | keys.2\.question.original        | Do you ship abroad?   |
+      | keys.$3\.question.original       | Where are you based?  |
+      | keys.0\.text.original            | Sign up               |
+      | keys.1\.text.original            | Log in                |
+
+  Scenario: Rosey generates base.json files from JSONPath rules
+    Given I have a "dist/site/api.json" file with the content:
+      """
+      {
+        "items": [
+          { "slug": "Boots", "title": "Walking boots" },
+          { "slug": "hats", "title": "Sun hats" }
+        ],
+        "footer": { "text": "Thanks for visiting" }
+      }
+      """
+    And I have a "dist/site/api.rosey.json" file with the content:
+      """
+      {
+        "$.items[*].title": "rosey:{$.items[*].slug}.title",
+        "$.footer.text": "rosey:footer",
+        "$.header.text": "rosey:header"
+      }
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.boots\.title.original | Walking boots       |
+      | keys.hats\.title.original  | Sun hats            |
+      | keys.footer.original       | Thanks for visiting |
+    And I should see 'Schema rule "$.header.text"' in stderr
+    And I should see "matched nothing" in stderr
+    And I should see "api.json" in stderr
+
+  Scenario: Rosey generate fails on a data file it can't parse
+    Given I have a "dist/site/data.json" file with the content:
+      """
+      { "title": "Missing a brace"
+      """
+    And I have a "dist/site/data.rosey.json" file with the content:
+      """
+      { "title": "rosey:title" }
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "Failed to parse" in stderr
+    And I should see "data.json" in stderr
+    And I should not see the file "rosey/base.json"
+
+  Scenario: Rosey generate warns about data files that don't match their schema
+    Given I have a "dist/site/data.json" file with the content:
+      """
+      { "title": "Hello", "tags": "not a list" }
+      """
+    And I have a "dist/site/data.rosey.json" file with the content:
+      """
+      { "title": "rosey:title", "tags": ["rosey:tag"] }
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.title.original | Hello |
+    And I should see "Warning: data.json: Schema mismatch" in stderr
@@ -0,0 +1,39 @@
+Feature: Rosey Generate Markdown
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+
+  Scenario: Rosey generates base.json files from Markdown
+    Given I have a "dist/site/about.md" file with the content:
+      """
+      ---
+      title: About us # rosey:title
+      layout: page
+      ---
+      # <!-- rosey:heading -->Who we are<!-- /rosey -->
+
+      <!-- rosey:intro -->
+      We make *lovely* things.
+      <!-- /rosey -->
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.title.original          | About us                 |
+      | keys.title.pages.about\.md   | int:1                    |
+      | keys.heading.original        | Who we are               |
+      | keys.intro.original          | We make *lovely* things. |
+
+  Scenario: Rosey generates namespaced keys from plain text
+    Given I have a "dist/site/llms.txt" file with the content:
+      """
+      <!-- rosey-ns:llms -->
+      <!-- rosey:summary -->A site about mushrooms.<!-- /rosey -->
+      Untagged text
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.llms\.summary.original | A site about mushrooms. |
+    And I should not see "Untagged text" in "rosey/base.json"
@@ -0,0 +1,66 @@
+Feature: Rosey Generate Max Lengths
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+    And I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <head>
+      <title data-rosey="title">Garden Shop</title>
+      </head>
+      <body>
+      <button data-rosey="cta" data-rosey-maxlength="12">Buy now</button>
+      <p data-rosey="body">Everything you need for the garden.</p>
+      </body>
+      </html>
+      """
+
+  Scenario: Rosey generate records max lengths from attributes
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.cta.maxlength | int:12 |
+    And I should not see '"maxlength": 60' in "rosey/base.json"
+
+  Scenario: Rosey generate records default max lengths for element types
+    Given I have a "rosey.yml" file with the content:
+      """
+      max_lengths:
+        title: 60
+        button: 20
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.title.maxlength | int:60 |
+      | keys.cta.maxlength   | int:12 |
+
+  Scenario: Rosey generate only limits attributes with their own max length
+    Given I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <img data-rosey="hero" data-rosey-attrs="alt,title" data-rosey-maxlength="12" data-rosey-maxlength-title="30" alt="A garden in the spring sunshine" title="Spring garden">
+      </body>
+      </html>
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.hero.maxlength         | int:12 |
+      | keys.hero\.title.maxlength  | int:30 |
+
+  Scenario: Rosey generate warns about invalid max lengths
+    Given I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <button data-rosey="cta" data-rosey-maxlength="abc">Buy now</button>
+      </body>
+      </html>
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see 'ignoring data-rosey-maxlength="abc" on <button>' in stderr
+    And I should not see '"maxlength"' in "rosey/base.json"
@@ -0,0 +1,34 @@
+Feature: Rosey Generate MessageFormat
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+
+	Scenario: Rosey generates message placeholders from element arguments
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p data-rosey="greeting" data-rosey-args='{"name": "Alice", "count": 3}'>Hello Alice, you have 3 messages</p>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| generate |
+		Then I should see "rosey/base.json" containing the values:
+			| keys.greeting.original | Hello {name}, you have {count} messages |
+
+	Scenario: Rosey generate warns about invalid element arguments and keeps the text as it is
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p data-rosey="greeting" data-rosey-args='{name: Alice}'>Hello Alice</p>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| generate |
+		Then I should see "rosey/base.json" containing the values:
+			| keys.greeting.original | Hello Alice |
+		And I should see 'Warning: index.html: ignoring data-rosey-args for "greeting", as it isn't a JSON object' in stderr
@@ -0,0 +1,37 @@
+Feature: Rosey Generate Plurals
+	Background:
+		Given I have the environment variables:
+			| ROSEY_SOURCE | dist/site            |
+			| ROSEY_DEST   | dist/translated_site |
+
+	Scenario: Rosey generates plural forms for counted elements in version 3
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p data-rosey="results" data-rosey-count="3">3 results</p>
+			<p data-rosey="results" data-rosey-count="1">1 result</p>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| generate    |
+			| --version 3 |
+		Then I should see "rosey/base.json" containing the values:
+			| version                      | int:3           |
+			| keys.results.original        | 3 results       |
+			| keys.results.plurals.one     | {count} result  |
+			| keys.results.plurals.other   | {count} results |
+
+	Scenario: Rosey does not generate plural forms in version 2
+		Given I have a "dist/site/index.html" file with the content:
+			"""
+			<html>
+			<body>
+			<p data-rosey="results" data-rosey-count="3">3 results</p>
+			</body>
+			</html>
+			"""
+		When I run my program with the flags:
+			| generate |
+		Then I should not see "plurals" in "rosey/base.json"
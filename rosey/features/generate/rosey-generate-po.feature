@@ -0,0 +1,59 @@
+Feature: Rosey Generate PO
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+
+  Scenario: Rosey does not generate a POT file by default
+    Given I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="seal">Kiss From A Rose</p>
+      </body>
+      </html>
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see the file "rosey/base.json"
+    But I should not see the file "rosey/base.pot"
+
+  Scenario: Rosey generates a POT file next to base.json
+    Given I have a "dist/site/index.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="seal">Kiss From A "Rose"</p>
+      </body>
+      </html>
+      """
+    And I have a "dist/site/about.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="seal">Kiss From A "Rose"</p>
+      </body>
+      </html>
+      """
+    When I run my program with the flags:
+      | generate |
+      | --pot    |
+    Then I should see the file "rosey/base.json"
+    And I should see '#: about.html' in "rosey/base.pot"
+    And I should see '#: index.html' in "rosey/base.pot"
+    And I should see 'msgctxt "seal"' in "rosey/base.pot"
+    And I should see 'msgid "Kiss From A \"Rose\""' in "rosey/base.pot"
+
+  Scenario: Rosey isolates POT references containing spaces
+    Given I have a "dist/site/about us.html" file with the content:
+      """
+      <html>
+      <body>
+      <p data-rosey="seal">Kiss From A Rose</p>
+      </body>
+      </html>
+      """
+    When I run my program with the flags:
+      | generate |
+      | --pot    |
+    Then I should see '#: ⁨about us.html⁩' in "rosey/base.pot"
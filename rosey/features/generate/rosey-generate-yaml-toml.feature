@@ -0,0 +1,44 @@
+Feature: Rosey Generate YAML and TOML
+  Background:
+    Given I have the environment variables:
+      | ROSEY_SOURCE | dist/site            |
+      | ROSEY_DEST   | dist/translated_site |
+
+  Scenario: Rosey generates base.json files from YAML
+    Given I have a "dist/site/search.yml" file with the content:
+      """
+      placeholder: Search the site
+      filters: [blue, green]
+      """
+    And I have a "dist/site/search.rosey.yml" file with the content:
+      """
+      placeholder: rosey:search.placeholder
+      filters: [rosey-array-ns|rosey:value]
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.search\.placeholder.original | Search the site |
+      | keys.blue\.value.original         | blue            |
+      | keys.green\.value.original        | green           |
+
+  Scenario: Rosey generates base.json files from TOML
+    Given I have a "dist/site/cms.toml" file with the content:
+      """
+      title = "My site"
+
+      [[collections]]
+      label = "Posts"
+      """
+    And I have a "dist/site/cms.rosey.toml" file with the content:
+      """
+      title = "rosey:cms.title"
+
+      [[collections]]
+      label = "rosey-ns|rosey:label"
+      """
+    When I run my program with the flags:
+      | generate |
+    Then I should see "rosey/base.json" containing the values:
+      | keys.cms\.title.original   | My site |
+      | keys.posts\.label.original | Posts   |
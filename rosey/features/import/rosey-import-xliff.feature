@@ -0,0 +1,85 @@
+Feature: Rosey Import XLIFF
+
+  Scenario: Rosey imports XLIFF 1.2 files into V2 locale files
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "frog": {
+            "original": "Ribbit",
+            "pages": {
+              "pond.html": 2
+            },
+            "total": 2
+          }
+        }
+      }
+      """
+    And I have a "rosey/locales/em.json" file with the content:
+      """
+      {
+        "seal": {
+          "original": "Kiss From A Rose",
+          "value": "🌹",
+          "pages": {
+            "index.html": 1
+          },
+          "total": 1
+        }
+      }
+      """
+    And I have a "rosey/exports/em.xlf" file with the content:
+      """
+      <?xml version="1.0" encoding="UTF-8"?>
+      <xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">
+        <file original="rosey" datatype="html" source-language="en" target-language="em">
+          <body>
+            <trans-unit id="seal">
+              <source>Kiss From A &lt;b&gt;Rose&lt;/b&gt;</source>
+              <target>👄&lt;b&gt;🌹&lt;/b&gt;</target>
+            </trans-unit>
+            <trans-unit id="frog">
+              <source>Ribbit</source>
+              <target>🐸</target>
+            </trans-unit>
+          </body>
+        </file>
+      </xliff>
+      """
+    When I run my program with the flags:
+      | import |
+    Then I should see "rosey/locales/em.json" containing the values:
+      | seal.original          | Kiss From A <b>Rose</b> |
+      | seal.value             | 👄<b>🌹</b>               |
+      | seal.pages.index\.html | int:1                   |
+      | seal.total             | int:1                   |
+      | frog.original          | Ribbit                  |
+      | frog.value             | 🐸                       |
+      | frog.total             | int:2                   |
+
+  Scenario: Rosey imports XLIFF 2.0 files into V1 locale files
+    Given I have a "rosey/locales/em.json" file with the content:
+      """
+      {
+        "seal": "🌹"
+      }
+      """
+    And I have a "rosey/exports/translations.xlf" file with the content:
+      """
+      <?xml version="1.0" encoding="UTF-8"?>
+      <xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="em">
+        <file id="rosey">
+          <unit id="seal">
+            <segment>
+              <source>Kiss From A Rose</source>
+              <target>👄🌹</target>
+            </segment>
+          </unit>
+        </file>
+      </xliff>
+      """
+    When I run my program with the flags:
+      | import |
+    Then I should see "rosey/locales/em.json" containing the values:
+      | seal | 👄🌹 |
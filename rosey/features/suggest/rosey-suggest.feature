@@ -0,0 +1,128 @@
+Feature: Rosey Suggest
+
+  Scenario: Rosey suggests exact and fuzzy translation memory matches
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "title": {
+            "original": "Welcome to our store",
+            "pages": { "index.html": 1 },
+            "total": 1
+          },
+          "cta": {
+            "original": "Buy now",
+            "pages": { "index.html": 1 },
+            "total": 1
+          },
+          "other": {
+            "original": "Something else entirely",
+            "pages": { "index.html": 1 },
+            "total": 1
+          }
+        }
+      }
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "title": {
+          "original": "Welcome to our shop",
+          "value": "Bienvenue dans notre boutique"
+        }
+      }
+      """
+    And I have a "rosey/memory.json" file with the content:
+      """
+      {
+        "fr": {
+          "Buy now": "Acheter maintenant"
+        }
+      }
+      """
+    When I run my program with the flags:
+      | suggest |
+    Then I should see "fr: 1 exact and 0 fuzzy suggestions" in stdout
+    And I should see "rosey/locales/fr.json" containing the values:
+      | cta.original   | Buy now                       |
+      | cta.value      | Acheter maintenant            |
+      | cta.total      | int:1                         |
+      | title.original | Welcome to our shop           |
+      | title.value    | Bienvenue dans notre boutique |
+      | cta.status     | needs-review                  |
+    And I should see "rosey/memory.json" containing the values:
+      | fr.Welcome to our shop | Bienvenue dans notre boutique |
+    And I should not see "other" in "rosey/locales/fr.json"
+
+  Scenario: Rosey suggests fuzzy matches for missing keys and keeps them outdated
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "title": {
+            "original": "Welcome to our shop",
+            "pages": { "index.html": 1 },
+            "total": 1
+          },
+          "about": {
+            "original": "Welcome to our store",
+            "pages": { "about.html": 1 },
+            "total": 1
+          }
+        }
+      }
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "title": {
+          "original": "Welcome to our shop",
+          "value": "Bienvenue dans notre boutique"
+        }
+      }
+      """
+    When I run my program with the flags:
+      | suggest |
+    Then I should see "fr: 0 exact and 1 fuzzy suggestions" in stdout
+    And I should see "rosey/locales/fr.json" containing the values:
+      | about.original | Welcome to our shop           |
+      | about.value    | Bienvenue dans notre boutique |
+      | about.status   | needs-review                  |
+    When I run my program with the flags:
+      | check |
+    Then I should see "rosey/checks.json" containing the values:
+      | fr.keys.about | outdated |
+
+  Scenario: Rosey only suggests fuzzy matches above the threshold
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "about": {
+            "original": "Welcome to our store",
+            "pages": { "about.html": 1 },
+            "total": 1
+          }
+        }
+      }
+      """
+    And I have a "rosey/memory.json" file with the content:
+      """
+      {
+        "fr": {
+          "Welcome to our shop": "Bienvenue dans notre boutique"
+        }
+      }
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {}
+      """
+    When I run my program with the flags:
+      | suggest           |
+      | --threshold 95    |
+    Then I should see "fr: 0 exact and 0 fuzzy suggestions" in stdout
+    And I should not see "about" in "rosey/locales/fr.json"
@@ -0,0 +1,77 @@
+Feature: Rosey Sync
+
+  Background:
+    Given I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "title": {
+            "original": "Welcome to our store",
+            "pages": { "index.html": 2 },
+            "total": 2
+          },
+          "cta": {
+            "original": "Buy now",
+            "pages": { "index.html": 1 },
+            "total": 1
+          }
+        }
+      }
+      """
+    And I have a "rosey/locales/fr.json" file with the content:
+      """
+      {
+        "title": {
+          "original": "Welcome to our shop",
+          "value": "Bienvenue dans notre boutique",
+          "pages": { "index.html": 1 },
+          "total": 1
+        },
+        "gone": {
+          "original": "Old",
+          "value": "Vieux"
+        }
+      }
+      """
+
+  Scenario: Rosey sync adds missing keys and refreshes outdated keys
+    When I run my program with the flags:
+      | sync |
+    Then I should see "fr: 1 added, 1 outdated, 0 pruned" in stdout
+    And I should see "rosey/locales/fr.json" containing the values:
+      | cta.original            | Buy now                       |
+      | cta.pages.index\.html   | int:1                         |
+      | cta.total               | int:1                         |
+      | title.original          | Welcome to our store          |
+      | title.previous_original | Welcome to our shop           |
+      | title.value             | Bienvenue dans notre boutique |
+      | title.total             | int:2                         |
+      | gone.value              | Vieux                         |
+    When I run my program with the flags:
+      | check |
+    Then I should see "rosey/checks.json" containing the values:
+      | fr.keys.title     | outdated |
+      | fr.keys.cta       | missing  |
+      | fr.states.missing | int:1 |
+      | fr.keys.gone      | unused   |
+
+  Scenario: Rosey sync can prune unused keys
+    When I run my program with the flags:
+      | sync           |
+      | --prune-unused |
+    Then I should see "fr: 1 added, 1 outdated, 1 pruned" in stdout
+    And I should not see "gone" in "rosey/locales/fr.json"
+
+  Scenario: Rosey sync rejects PO locale files
+    Given I have a "rosey/locales/de.po" file with the content:
+      """
+      msgctxt "title"
+      msgid "Welcome to our store"
+      msgstr "Willkommen in unserem Laden"
+      """
+    When I run my program with the flags:
+      | sync |
+    Then I should see "rosey sync can't update gettext locale files" in stderr
+    And I should see "rosey/locales/fr.json" containing the values:
+      | title.original | Welcome to our shop |
@@ -0,0 +1,194 @@
+Feature: Rosey Translate
+  Background:
+    Given I have a "rosey.yml" file with the content:
+      """
+      providers:
+        pirate:
+          type: command
+          command: sed
+          args: ["s/Hello/Ahoy/; s/World/Matey/"]
+        broken:
+          type: command
+          command: sh
+          args: ["-c", "echo 'Quota exceeded' >&2; exit 1"]
+      """
+    And I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "title": {
+            "original": "Hello World",
+            "pages": { "index.html": 1 },
+            "total": 1
+          },
+          "link": {
+            "original": "Hello <a title='World'>World</a>",
+            "pages": { "index.html": 1 },
+            "total": 1
+          },
+          "done": {
+            "original": "Hello there",
+            "pages": { "index.html": 1 },
+            "total": 1
+          },
+          "count": {
+            "original": "{count, plural, one {# World} other {# Worlds}}",
+            "pages": { "index.html": 1 },
+            "total": 1
+          }
+        }
+      }
+      """
+    And I have a "rosey/locales/de.json" file with the content:
+      """
+      {
+        "done": {
+          "original": "Hello there",
+          "value": "Hallo zusammen"
+        }
+      }
+      """
+
+  Scenario: Rosey translate fills missing values with a command provider
+    When I run my program with the flags:
+      | translate             |
+      | --locale de           |
+      | --provider pirate     |
+    Then I should see "de: 2 machine translations from pirate, 1 skipped" in stdout
+    And I should see "rosey/locales/de.json" containing the values:
+      | title.original           | Hello World                      |
+      | title.value              | Ahoy Matey                       |
+      | title.status             | draft                            |
+      | title.translator         | pirate                           |
+      | title.machine_translated | bool:true                        |
+      | title.total              | int:1                            |
+      | link.value               | Ahoy <a title='World'>Matey</a>  |
+      | done.value               | Hallo zusammen                   |
+    And I should not see "machine_translated" in "rosey/base.json"
+
+  Scenario: Rosey translate creates a locale file for a new locale
+    When I run my program with the flags:
+      | translate             |
+      | --locale fr           |
+      | --provider pirate     |
+    Then I should see "rosey/locales/fr.json" containing the values:
+      | done.value               | Ahoy there |
+      | done.machine_translated  | bool:true  |
+
+  Scenario: Rosey translate reports provider failures
+    When I run my program with the flags:
+      | translate             |
+      | --locale de           |
+      | --provider broken     |
+    Then I should see "Translation provider 'broken' failed: sh exited with exit status: 1: Quota exceeded" in stderr
+
+  Scenario: Rosey translate keeps finished batches when the provider fails
+    Given I have a "rosey.yml" file with the content:
+      """
+      providers:
+        flaky:
+          type: command
+          command: sh
+          args: ["-c", "grep -q Goodbye && exit 1; echo Ahoy"]
+      """
+    And I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "k01": { "original": "Hello 1" },
+          "k02": { "original": "Hello 2" },
+          "k03": { "original": "Hello 3" },
+          "k04": { "original": "Hello 4" },
+          "k05": { "original": "Hello 5" },
+          "k06": { "original": "Hello 6" },
+          "k07": { "original": "Hello 7" },
+          "k08": { "original": "Hello 8" },
+          "k09": { "original": "Hello 9" },
+          "k10": { "original": "Hello 10" },
+          "k11": { "original": "Hello 11" },
+          "k12": { "original": "Hello 12" },
+          "k13": { "original": "Hello 13" },
+          "k14": { "original": "Hello 14" },
+          "k15": { "original": "Hello 15" },
+          "k16": { "original": "Hello 16" },
+          "k17": { "original": "Hello 17" },
+          "k18": { "original": "Hello 18" },
+          "k19": { "original": "Hello 19" },
+          "k20": { "original": "Hello 20" },
+          "k21": { "original": "Hello 21" },
+          "k22": { "original": "Hello 22" },
+          "k23": { "original": "Hello 23" },
+          "k24": { "original": "Hello 24" },
+          "k25": { "original": "Hello 25" },
+          "k26": { "original": "Hello 26" },
+          "k27": { "original": "Hello 27" },
+          "k28": { "original": "Hello 28" },
+          "k29": { "original": "Hello 29" },
+          "k30": { "original": "Hello 30" },
+          "k31": { "original": "Hello 31" },
+          "k32": { "original": "Hello 32" },
+          "k33": { "original": "Hello 33" },
+          "k34": { "original": "Hello 34" },
+          "k35": { "original": "Hello 35" },
+          "k36": { "original": "Hello 36" },
+          "k37": { "original": "Hello 37" },
+          "k38": { "original": "Hello 38" },
+          "k39": { "original": "Hello 39" },
+          "k40": { "original": "Hello 40" },
+          "k41": { "original": "Hello 41" },
+          "k42": { "original": "Hello 42" },
+          "k43": { "original": "Hello 43" },
+          "k44": { "original": "Hello 44" },
+          "k45": { "original": "Hello 45" },
+          "k46": { "original": "Hello 46" },
+          "k47": { "original": "Hello 47" },
+          "k48": { "original": "Hello 48" },
+          "k49": { "original": "Hello 49" },
+          "k50": { "original": "Hello 50" },
+          "later": { "original": "Goodbye" }
+        }
+      }
+      """
+    When I run my program with the flags:
+      | translate             |
+      | --locale fr           |
+      | --provider flaky      |
+    Then I should see "Translation provider 'flaky' failed" in stderr
+    And I should see "rosey/locales/fr.json" containing the values:
+      | k01.value | Ahoy |
+      | k50.value | Ahoy |
+    And I should not see "Goodbye" in "rosey/locales/fr.json"
+
+  Scenario: Rosey translate needs a configured provider
+    When I run my program with the flags:
+      | translate             |
+      | --locale de           |
+      | --provider missing    |
+    Then I should see "Unknown translation provider 'missing'. Configured providers:" in stderr
+
+  Scenario: Rosey translate fails when every translation is skipped
+    Given I have a "rosey.yml" file with the content:
+      """
+      providers:
+        lossy:
+          type: command
+          command: sed
+          args: ["s/.*/Ahoy/"]
+      """
+    And I have a "rosey/base.json" file with the content:
+      """
+      {
+        "version": 2,
+        "keys": {
+          "link": { "original": "Hello <a>World</a>" }
+        }
+      }
+      """
+    When I run my program with the flags:
+      | translate             |
+      | --locale fr           |
+      | --provider lossy      |
+    Then I should see "Translation provider 'lossy' failed: none of the missing fr translations could be machine translated, 1 skipped" in stderr
+    And I should not see the file "rosey/locales/fr.json"
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+
+use icu_locale::{LanguageIdentifier, LocaleDirectionality};
+
+// Configured directions win over the script direction from CLDR
+pub fn is_rtl_locale(locale: &str, directions: &BTreeMap<String, String>) -> bool {
+    let language = locale.split(['-', '_']).next().unwrap_or(locale);
+    if let Some(direction) = directions.get(locale).or_else(|| directions.get(language)) {
+        return direction == "rtl";
+    }
+
+    locale
+        .replace('_', "-")
+        .parse::<LanguageIdentifier>()
+        .is_ok_and(|id| LocaleDirectionality::new_extended().is_right_to_left(&id))
+}
+
+// `Some(true)` for strong right-to-left characters, `Some(false)` for strong
+// left-to-right characters, and `None` for anything without a direction
+fn strong_direction(c: char) -> Option<bool> {
+    match c as u32 {
+        0x0590..=0x08FF
+        | 0xFB1D..=0xFDFF
+        | 0xFE70..=0xFEFF
+        | 0x10800..=0x10FFF
+        | 0x1E800..=0x1EFFF => Some(true),
+        _ if c.is_alphabetic() => Some(false),
+        _ => None,
+    }
+}
+
+fn text_directions(text: &str) -> (bool, bool) {
+    let mut in_tag = false;
+    let (mut ltr, mut rtl) = (false, false);
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if in_tag => {}
+            _ => match strong_direction(c) {
+                Some(true) => rtl = true,
+                Some(false) => ltr = true,
+                None => {}
+            },
+        }
+    }
+    (ltr, rtl)
+}
+
+// Text that only runs against the direction of the page
+pub fn is_opposite_direction(text: &str, rtl: bool) -> bool {
+    let (has_ltr, has_rtl) = text_directions(text);
+    if rtl {
+        has_ltr && !has_rtl
+    } else {
+        has_rtl && !has_ltr
+    }
+}
+
+// Wraps each run of text against the direction of the page in a <bdi>, so
+// that neutral characters around it keep their place
+pub fn isolate_opposite_runs(text: &str, rtl: bool) -> Option<String> {
+    let chars = text.char_indices().collect::<Vec<_>>();
+    let directions = chars
+        .iter()
+        .map(|(_, c)| strong_direction(*c))
+        .collect::<Vec<_>>();
+    if !directions.contains(&Some(rtl)) || !directions.contains(&Some(!rtl)) {
+        return None;
+    }
+    // Numbers stay with left-to-right runs on right-to-left pages
+    let extends_run =
+        |i: usize| directions[i] == Some(!rtl) || (rtl && chars[i].1.is_ascii_digit());
+
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        if directions[i] != Some(!rtl) {
+            i += 1;
+            continue;
+        }
+
+        let mut end = i;
+        let mut j = i + 1;
+        while j < chars.len() && directions[j] != Some(rtl) {
+            if extends_run(j) {
+                end = j;
+            }
+            j += 1;
+        }
+
+        let start_byte = chars[i].0;
+        let end_byte = chars[end].0 + chars[end].1.len_utf8();
+        output.push_str(&text[last..start_byte]);
+        output.push_str("<bdi>");
+        output.push_str(&text[start_byte..end_byte]);
+        output.push_str("</bdi>");
+        last = end_byte;
+        i = end + 1;
+    }
+    output.push_str(&text[last..]);
+    Some(output)
+}
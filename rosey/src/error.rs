@@ -0,0 +1,139 @@
+use std::{fmt::Display, path::PathBuf, sync::Mutex};
+
+#[derive(Debug)]
+pub enum RoseyError {
+    MissingSource,
+    InvalidConfig(String),
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+    CheckFailed(Vec<RoseyCheckFailure>),
+    OverlongTranslations(usize),
+    Provider {
+        provider: String,
+        message: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct RoseyCheckFailure {
+    pub locale: String,
+    pub state: String,
+    pub count: i32,
+}
+
+impl RoseyError {
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> RoseyError {
+        RoseyError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub fn parse(path: impl Into<PathBuf>, message: impl Display) -> RoseyError {
+        RoseyError::Parse {
+            path: path.into(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn provider(provider: impl Into<String>, message: impl Display) -> RoseyError {
+        RoseyError::Provider {
+            provider: provider.into(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for RoseyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoseyError::MissingSource => write!(
+                f,
+                "Rosey requires a source directory to process. Provide either: \n\
+                       • A `--source <PATH>` CLI flag \n\
+                       • A `source` key in a rosey.yml, rosey.toml, or rosey.json file \n\
+                       • A `ROSEY_SOURCE` environment variable"
+            ),
+            RoseyError::InvalidConfig(message) => write!(f, "{message}"),
+            RoseyError::Io { path, source } => write!(f, "Failed to access {path:?}: {source}"),
+            RoseyError::Parse { path, message } => write!(f, "Failed to parse {path:?}: {message}"),
+            RoseyError::CheckFailed(failures) => {
+                write!(f, "Rosey check failed:")?;
+                for RoseyCheckFailure {
+                    locale,
+                    state,
+                    count,
+                } in failures
+                {
+                    write!(f, "\n  {locale}: {count} {state} translations")?;
+                }
+                Ok(())
+            }
+            RoseyError::OverlongTranslations(count) => write!(
+                f,
+                "Rosey build failed: {count} translations are longer than their maximum length"
+            ),
+            RoseyError::Provider { provider, message } => {
+                write!(f, "Translation provider '{provider}' failed: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RoseyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RoseyError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// A problem Rosey worked around without stopping, such as a data file that
+// doesn't match its schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoseyWarning {
+    pub file: String,
+    pub message: String,
+}
+
+impl Display for RoseyWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.file, self.message)
+    }
+}
+
+// Warnings collected while files are processed in parallel, handed to the caller
+// when the command finishes. Files processed once per locale warn only once.
+#[derive(Default)]
+pub(crate) struct RoseyWarnings(Mutex<Vec<RoseyWarning>>);
+
+impl RoseyWarnings {
+    pub fn warn(&self, file: &str, message: impl Display) {
+        let warning = RoseyWarning {
+            file: file.to_string(),
+            message: message.to_string(),
+        };
+        let mut warnings = self.0.lock().unwrap();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    pub fn take(&self) -> Vec<RoseyWarning> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+
+    // The CLI prints warnings as they're handed over
+    pub fn print(&self) {
+        for warning in self.take() {
+            eprintln!("Warning: {warning}");
+        }
+    }
+}
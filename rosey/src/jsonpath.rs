@@ -0,0 +1,224 @@
+use serde_json::Value;
+
+#[derive(Clone, Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+pub struct RoseyRuleMatch {
+    pub pointer: String,
+    pub key: String,
+}
+
+// Supports the subset of JSONPath needed to pick values out of a file:
+// `$`, `.key`, `['key']`, `[0]`, `.*` and `[*]`
+fn parse_path(path: &str) -> Option<Vec<PathSegment>> {
+    let mut chars = path.trim().strip_prefix('$')?.chars().peekable();
+    let mut segments = vec![];
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| *c != '.' && *c != '[') {
+                    name.push(c);
+                }
+                segments.push(match name.as_str() {
+                    "" => return None,
+                    "*" => PathSegment::Wildcard,
+                    _ => PathSegment::Key(name),
+                });
+            }
+            '[' => {
+                let segment = match chars.next_if(|c| *c == '\'' || *c == '"') {
+                    Some(quote) => {
+                        let mut name = String::new();
+                        loop {
+                            match chars.next()? {
+                                c if c == quote => break,
+                                c => name.push(c),
+                            }
+                        }
+                        PathSegment::Key(name)
+                    }
+                    None => {
+                        let mut inner = String::new();
+                        while let Some(c) = chars.next_if(|c| *c != ']') {
+                            inner.push(c);
+                        }
+                        match inner.trim() {
+                            "*" => PathSegment::Wildcard,
+                            index => PathSegment::Index(index.parse().ok()?),
+                        }
+                    }
+                };
+                chars.next_if_eq(&']')?;
+                segments.push(segment);
+            }
+            _ => return None,
+        }
+    }
+    Some(segments)
+}
+
+fn find_matches(
+    value: &Value,
+    segments: &[PathSegment],
+    current: &mut Vec<PathSegment>,
+    matches: &mut Vec<Vec<PathSegment>>,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        matches.push(current.clone());
+        return;
+    };
+
+    let mut step = |child: &Value, concrete: PathSegment| {
+        current.push(concrete);
+        find_matches(child, rest, current, matches);
+        current.pop();
+    };
+    match (segment, value) {
+        (PathSegment::Key(key), Value::Object(map)) => {
+            if let Some(child) = map.get(key) {
+                step(child, PathSegment::Key(key.clone()));
+            }
+        }
+        (PathSegment::Index(index), Value::Array(items)) => {
+            if let Some(child) = items.get(*index) {
+                step(child, PathSegment::Index(*index));
+            }
+        }
+        (PathSegment::Wildcard, Value::Object(map)) => {
+            for (key, child) in map {
+                step(child, PathSegment::Key(key.clone()));
+            }
+        }
+        (PathSegment::Wildcard, Value::Array(items)) => {
+            for (index, child) in items.iter().enumerate() {
+                step(child, PathSegment::Index(index));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn json_pointer(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+            PathSegment::Index(index) => format!("/{index}"),
+            PathSegment::Wildcard => "/*".to_string(),
+        })
+        .collect()
+}
+
+fn display_path(path: &[PathSegment]) -> String {
+    let mut output = String::from("$");
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) if key.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+                output.push('.');
+                output.push_str(key);
+            }
+            PathSegment::Key(key) => output.push_str(&format!("['{key}']")),
+            PathSegment::Index(index) => output.push_str(&format!("[{index}]")),
+            PathSegment::Wildcard => output.push_str("[*]"),
+        }
+    }
+    output
+}
+
+// Fills each `{path}` in a key with the value it points to, where any
+// wildcards in the path take the positions of the matched value
+fn resolve_key(source: &Value, template: &str, matched: &[PathSegment]) -> Result<String, String> {
+    let mut key = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        key.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}').map(|end| end + start) else {
+            return Err(format!("has an unclosed {{ in its key {template:?}"));
+        };
+        let path = &rest[start + 1..end];
+        let Some(segments) = parse_path(path) else {
+            return Err(format!("has an invalid JSONPath {path:?} in its key"));
+        };
+
+        let concrete = segments
+            .into_iter()
+            .enumerate()
+            .map(|(index, segment)| match segment {
+                PathSegment::Wildcard => matched.get(index).cloned().unwrap_or(segment),
+                segment => segment,
+            })
+            .collect::<Vec<_>>();
+        match source.pointer(&json_pointer(&concrete)) {
+            Some(Value::String(value)) => key.push_str(&value.to_lowercase()),
+            Some(Value::Number(value)) => key.push_str(&value.to_string()),
+            _ => {
+                return Err(format!(
+                    "has no value for {path:?} at {}",
+                    display_path(&concrete)
+                ))
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+
+    key.push_str(rest);
+    Ok(key)
+}
+
+// A schema made of JSONPath rules, such as `{ "$.items[*].title": "rosey:{$.items[*].slug}.title" }`
+pub fn schema_rules(schema: &Value) -> Option<Vec<(&String, &Value)>> {
+    let Value::Object(rules) = schema else {
+        return None;
+    };
+    (!rules.is_empty() && rules.keys().all(|path| path.trim().starts_with('$')))
+        .then(|| rules.iter().collect())
+}
+
+pub fn rule_matches(
+    source: &Value,
+    path: &str,
+    directive: &Value,
+) -> (Vec<RoseyRuleMatch>, Vec<String>) {
+    let Some(template) = directive
+        .as_str()
+        .and_then(|d| d.trim().strip_prefix("rosey:"))
+    else {
+        return (
+            vec![],
+            vec!["should have a value of rosey:<key>".to_string()],
+        );
+    };
+    let Some(segments) = parse_path(path) else {
+        return (vec![], vec!["is not a valid JSONPath".to_string()]);
+    };
+
+    let mut paths = vec![];
+    find_matches(source, &segments, &mut vec![], &mut paths);
+    if paths.is_empty() {
+        return (vec![], vec!["matched nothing".to_string()]);
+    }
+
+    let mut matches = vec![];
+    let mut errors = vec![];
+    for matched in paths {
+        let pointer = json_pointer(&matched);
+        if !source.pointer(&pointer).is_some_and(Value::is_string) {
+            errors.push(format!(
+                "matched a value that is not a string at {}",
+                display_path(&matched)
+            ));
+            continue;
+        }
+        match resolve_key(source, template, &matched) {
+            Ok(key) => matches.push(RoseyRuleMatch { pointer, key }),
+            Err(error) => errors.push(error),
+        }
+    }
+    (matches, errors)
+}
@@ -1,20 +1,49 @@
+mod direction;
+pub mod error;
+mod jsonpath;
+mod locale_format;
+mod markdown;
+mod messageformat;
 pub mod options;
+mod plurals;
 mod runners;
+mod wrap;
 
 use crate::runners::generator::RoseyGenerator;
 use anyhow::{bail, Error};
 use clap::ArgMatches;
+pub use error::*;
 pub use options::*;
-use runners::{builder::RoseyBuilder, checker::RoseyChecker};
+use regex::Regex;
+use runners::{
+    builder::RoseyBuilder, checker::RoseyChecker, exporter::RoseyExporter, importer::RoseyImporter,
+    suggester::RoseySuggester, translator::RoseyTranslator,
+};
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, env, path::PathBuf, str::FromStr};
 
-const SUPPORTED_WRAP_LANGS: [&str; 4] = ["ja", "he", "th", "zh"];
+const SUPPORTED_WRAP_LANGS: [&str; 8] = ["ja", "he", "th", "zh", "ko", "km", "lo", "my"];
+const SUPPORTED_WRAP_SCRIPTS: [&str; 9] = [
+    "Jpan", "Hans", "Hant", "Hebr", "Thai", "Kore", "Khmr", "Laoo", "Mymr",
+];
+const SUPPORTED_WRAP_MODES: [&str; 3] = ["span", "wbr", "zwsp"];
+const SUPPORTED_EXPORT_FORMATS: [&str; 1] = ["xliff"];
+const SUPPORTED_XLIFF_VERSIONS: [&str; 2] = ["1.2", "2.0"];
+const SUPPORTED_REDIRECT_FORMATS: [&str; 3] = ["netlify", "vercel", "nginx"];
+const SUPPORTED_CHECK_FORMATS: [&str; 3] = ["json", "junit", "markdown"];
+const SUPPORTED_FAIL_ON_STATES: [&str; 5] =
+    ["outdated", "mismatched", "missing", "unused", "overlong"];
+const SUPPORTED_REVIEW_STATUSES: [&str; 3] = ["draft", "needs-review", "approved"];
 
 pub enum RoseyCommand {
     Generate,
     Build,
     Check,
+    Export,
+    Import,
+    Suggest,
+    Sync,
+    Translate,
 }
 
 impl FromStr for RoseyCommand {
@@ -25,6 +54,11 @@ impl FromStr for RoseyCommand {
             "generate" => Ok(RoseyCommand::Generate),
             "build" => Ok(RoseyCommand::Build),
             "check" => Ok(RoseyCommand::Check),
+            "export" => Ok(RoseyCommand::Export),
+            "import" => Ok(RoseyCommand::Import),
+            "suggest" => Ok(RoseyCommand::Suggest),
+            "sync" => Ok(RoseyCommand::Sync),
+            "translate" => Ok(RoseyCommand::Translate),
             other => bail!("Unsupported subcommand: {other}"),
         }
     }
@@ -82,77 +116,103 @@ impl RoseyOptions {
             }
         };
 
-        let original_source = matches.get("source", base.source.clone());
+        let source = matches.get("source", base.source);
         let dest = matches.get("dest", base.dest);
         let working_dir = env::current_dir().unwrap_or_else(|e| {
             eprintln!("Couldn't access the current working directory: {e}");
             std::process::exit(1);
         });
 
-        if !matches!(subcommand, RoseyCommand::Check)
-            && original_source.to_string_lossy().is_empty()
-        {
-            eprintln!(
-                "Rosey requires a source directory to process. Provide either: \n\
-                       • A `--source <PATH>` CLI flag \n\
-                       • A `source` key in a rosey.yml, rosey.toml, or rosey.json file \n\
-                       • A `ROSEY_SOURCE` environment variable"
-            );
-            std::process::exit(1);
-        }
-
-        let options = RoseyOptions {
-            working_directory: working_dir.clone(),
-            serve: matches.is_present("serve"),
-            config: RoseyPublicConfig {
-                source: working_dir.join(matches.get("source", base.source)),
-                dest: working_dir.join(match dest.to_string_lossy().len() {
-                    0 => {
-                        let source = original_source.to_string_lossy();
-                        let source = source.trim_end_matches(['/', '\\']);
-                        PathBuf::from(&format!("{source}_translated"))
-                    }
-                    _ => dest,
-                }),
-                version: match matches
-                    .try_get_one("version")
-                    .map(|o| o.map(|s: &String| s.parse()))
-                {
-                    Ok(Some(Ok(v))) => v,
-                    _ => base.version,
-                },
-                tag: matches.get("tag", base.tag),
-                separator: matches.get("separator", base.separator),
-                locales: working_dir.join(matches.get("locales", base.locales)),
-                base: working_dir.join(matches.get("base", base.base)),
-                base_url: matches.get("base-url", base.base_url),
-                base_urls: working_dir.join(matches.get("base-urls", base.base_urls)),
-                default_language: matches.get("default-language", base.default_language),
-                default_language_at_root: matches.is_present("default-language-at-root")
-                    || base.default_language_at_root,
-                redirect_page: matches
-                    .get_opt("redirect-page", base.redirect_page)
-                    .map(|p| working_dir.join(p)),
-                exclusions: matches.get("exclusions", base.exclusions),
-                images_source: matches
-                    .get_opt("images-source", base.images_source)
-                    .map(|p| working_dir.join(p)),
-                wrap: match matches.try_get_many("wrap") {
-                    Ok(Some(langs)) => Some(langs.map(|l: &String|{
-                        if !SUPPORTED_WRAP_LANGS.iter().any(|lang| l.starts_with(lang)) {
-                            eprintln!("Cannot wrap text for language '{l}'. Languages with supported text wrapping: {SUPPORTED_WRAP_LANGS:?}");
-                            std::process::exit(1);
-                        }
-                        l.to_owned()
-                    }).collect()),
-                    _ => base.wrap,
-                },
-                wrap_class: matches.get_opt("wrap-class", base.wrap_class),
-                verbose: matches.is_present("verbose") || base.verbose,
-                languages: None, // TODO
+        let config = RoseyPublicConfig {
+            source: match source.to_string_lossy().len() {
+                0 => source,
+                _ => working_dir.join(source),
+            },
+            dest: match dest.to_string_lossy().len() {
+                0 => dest,
+                _ => working_dir.join(dest),
+            },
+            version: match matches
+                .try_get_one("version")
+                .map(|o| o.map(|s: &String| s.parse()))
+            {
+                Ok(Some(Ok(v))) => v,
+                _ => base.version,
             },
+            tag: matches.get("tag", base.tag),
+            separator: matches.get("separator", base.separator),
+            locales: working_dir.join(matches.get("locales", base.locales)),
+            base: working_dir.join(matches.get("base", base.base)),
+            base_url: matches.get("base-url", base.base_url),
+            base_urls: working_dir.join(matches.get("base-urls", base.base_urls)),
+            default_language: matches.get("default-language", base.default_language),
+            default_language_at_root: matches.is_present("default-language-at-root")
+                || base.default_language_at_root,
+            redirect_page: matches
+                .get_opt("redirect-page", base.redirect_page)
+                .map(|p| working_dir.join(p)),
+            redirects: match matches.try_get_many("redirects") {
+                Ok(Some(formats)) => Some(formats.cloned().collect()),
+                _ => base.redirects,
+            },
+            skip_redirect_pages: matches.is_present("skip-redirect-pages")
+                || base.skip_redirect_pages,
+            exclusions: matches.get("exclusions", base.exclusions),
+            images_source: matches
+                .get_opt("images-source", base.images_source)
+                .map(|p| working_dir.join(p)),
+            wrap: match matches.try_get_many("wrap") {
+                Ok(Some(langs)) => Some(langs.cloned().collect()),
+                _ => base.wrap,
+            },
+            wrap_class: matches.get_opt("wrap-class", base.wrap_class),
+            wrap_mode: matches.get("wrap-mode", base.wrap_mode),
+            exports: working_dir.join(matches.get("exports", base.exports)),
+            export_format: match subcommand {
+                RoseyCommand::Export => matches.get("format", base.export_format),
+                _ => base.export_format,
+            },
+            xliff_version: matches.get("xliff-version", base.xliff_version),
+            pot: matches.is_present("pot") || base.pot,
+            context: matches.is_present("context") || base.context,
+            check_format: match subcommand {
+                RoseyCommand::Check => matches.get("format", base.check_format),
+                _ => base.check_format,
+            },
+            fail_on: match matches.try_get_many("fail-on") {
+                Ok(Some(states)) => Some(states.cloned().collect()),
+                _ => base.fail_on,
+            },
+            sitemap: matches.is_present("sitemap") || base.sitemap,
+            incremental: matches.is_present("incremental") || base.incremental,
+            build_cache: working_dir.join(matches.get("build-cache", base.build_cache)),
+            fallbacks: base.fallbacks,
+            directions: base.directions,
+            max_lengths: base.max_lengths,
+            strict: matches.is_present("strict") || base.strict,
+            min_status: matches.get_opt("min-status", base.min_status),
+            memory: working_dir.join(matches.get("memory", base.memory)),
+            prune_unused: matches.is_present("prune-unused") || base.prune_unused,
+            suggest_threshold: match matches
+                .try_get_one("threshold")
+                .map(|o| o.map(|s: &String| s.parse()))
+            {
+                Ok(Some(Ok(t))) => t,
+                _ => base.suggest_threshold,
+            },
+            translate_locale: matches.get_opt("locale", base.translate_locale),
+            provider: matches.get_opt("provider", base.provider),
+            providers: base.providers,
+            verbose: matches.is_present("verbose") || base.verbose,
+            languages: None, // TODO
         };
 
+        let mut options = RoseyOptions::from_config(config, subcommand).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        options.serve = matches.is_present("serve");
+
         if matches.is_present("config-dump") {
             println!("{}", options.config);
             std::process::exit(0);
@@ -161,21 +221,246 @@ impl RoseyOptions {
         options
     }
 
-    pub async fn run(self, command: RoseyCommand) {
+    pub fn from_config(
+        mut config: RoseyPublicConfig,
+        subcommand: &RoseyCommand,
+    ) -> Result<RoseyOptions, RoseyError> {
+        let working_directory =
+            env::current_dir().map_err(|e| RoseyError::io(PathBuf::from("."), e))?;
+
+        if matches!(subcommand, RoseyCommand::Generate | RoseyCommand::Build)
+            && config.source.to_string_lossy().is_empty()
+        {
+            return Err(RoseyError::MissingSource);
+        }
+
+        if config.dest.to_string_lossy().is_empty() {
+            let source = config.source.to_string_lossy();
+            let source = source.trim_end_matches(['/', '\\']);
+            config.dest = PathBuf::from(&format!("{source}_translated"));
+        }
+
+        config.validate()?;
+
+        Ok(RoseyOptions {
+            working_directory,
+            serve: false,
+            config,
+        })
+    }
+
+    pub async fn run(self, command: RoseyCommand) -> Result<(), RoseyError> {
         match command {
-            RoseyCommand::Generate => RoseyGenerator::from(self).run(),
+            RoseyCommand::Generate => {
+                let mut generator = RoseyGenerator::from(self);
+                let result = generator.run();
+                generator.warnings.print();
+                result
+            }
             RoseyCommand::Build => RoseyBuilder::from(self).run().await,
-            RoseyCommand::Check => RoseyChecker::from(self).run(),
+            RoseyCommand::Check => {
+                let mut checker = RoseyChecker::from(self);
+                let result = checker.run();
+                if matches!(result, Ok(()) | Err(RoseyError::CheckFailed(_))) {
+                    print!("{}", checker.summary_table());
+                }
+                result
+            }
+            RoseyCommand::Export => RoseyExporter::from(self).run(),
+            RoseyCommand::Import => RoseyImporter::from(self).run(),
+            RoseyCommand::Suggest => RoseySuggester::from(self).run(),
+            RoseyCommand::Sync => RoseyChecker::from(self).sync(),
+            RoseyCommand::Translate => RoseyTranslator::from(self).run(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl RoseyPublicConfig {
+    pub fn validate(&self) -> Result<(), RoseyError> {
+        if let Some(lang) = self.wrap.iter().flatten().find(|l| {
+            !SUPPORTED_WRAP_LANGS.iter().any(|lang| l.starts_with(lang))
+                && !SUPPORTED_WRAP_SCRIPTS
+                    .iter()
+                    .any(|script| l.eq_ignore_ascii_case(script))
+        }) {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Cannot wrap text for language '{lang}'. Languages with supported text wrapping: {SUPPORTED_WRAP_LANGS:?}, or scripts: {SUPPORTED_WRAP_SCRIPTS:?}"
+            )));
+        }
+
+        if let Err(e) = Regex::new(&self.exclusions) {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Invalid exclusions regex '{}': {e}",
+                self.exclusions
+            )));
+        }
+
+        if !SUPPORTED_WRAP_MODES.contains(&self.wrap_mode.as_str()) {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Cannot wrap text with mode '{}'. Supported wrap modes: {SUPPORTED_WRAP_MODES:?}",
+                self.wrap_mode
+            )));
+        }
+
+        if !SUPPORTED_EXPORT_FORMATS.contains(&self.export_format.as_str()) {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Cannot export to format '{}'. Supported export formats: {SUPPORTED_EXPORT_FORMATS:?}",
+                self.export_format
+            )));
+        }
+
+        if !SUPPORTED_XLIFF_VERSIONS.contains(&self.xliff_version.as_str()) {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Unsupported XLIFF version '{}'. Supported XLIFF versions: {SUPPORTED_XLIFF_VERSIONS:?}",
+                self.xliff_version
+            )));
+        }
+
+        if let Some(format) = self
+            .redirects
+            .iter()
+            .flatten()
+            .find(|format| !SUPPORTED_REDIRECT_FORMATS.contains(&format.as_str()))
+        {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Cannot write redirects for '{format}'. Supported redirect formats: {SUPPORTED_REDIRECT_FORMATS:?}"
+            )));
+        }
+
+        if !SUPPORTED_CHECK_FORMATS.contains(&self.check_format.as_str()) {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Cannot output checks in format '{}'. Supported check formats: {SUPPORTED_CHECK_FORMATS:?}",
+                self.check_format
+            )));
+        }
+
+        if let Some(locale) = self
+            .fallbacks
+            .iter()
+            .find_map(|(locale, fallbacks)| fallbacks.contains(locale).then_some(locale))
+        {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Locale '{locale}' cannot fall back to itself."
+            )));
+        }
+
+        if let Some((locale, direction)) = self
+            .directions
+            .iter()
+            .find(|(_, direction)| !matches!(direction.as_str(), "ltr" | "rtl"))
+        {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Cannot set the direction of locale '{locale}' to '{direction}'. Supported directions: [\"ltr\", \"rtl\"]"
+            )));
+        }
+
+        if self.sitemap && self.base_url.is_empty() {
+            return Err(RoseyError::InvalidConfig(
+                "Writing a sitemap requires a base URL, as sitemaps must use absolute URLs. Set one with --base-url".to_string(),
+            ));
+        }
+
+        if let Some(provider) = self
+            .provider
+            .as_ref()
+            .filter(|provider| !self.providers.contains_key(*provider))
+        {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Unknown translation provider '{provider}'. Configured providers: {:?}",
+                self.providers.keys().collect::<Vec<_>>()
+            )));
+        }
+
+        if self.suggest_threshold > 100 {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Suggestion threshold must be a percentage, got {}.",
+                self.suggest_threshold
+            )));
+        }
+
+        if let Some(status) = self
+            .min_status
+            .iter()
+            .find(|status| review_rank(status).is_none())
+        {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Unknown review status '{status}'. Supported review statuses: {SUPPORTED_REVIEW_STATUSES:?}"
+            )));
+        }
+
+        if let Some(state) = self
+            .fail_on
+            .iter()
+            .flatten()
+            .find(|state| !SUPPORTED_FAIL_ON_STATES.contains(&state.as_str()))
+        {
+            return Err(RoseyError::InvalidConfig(format!(
+                "Cannot fail on check state '{state}'. Supported check states: {SUPPORTED_FAIL_ON_STATES:?}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the problems Rosey worked around, such as data files that don't match their schema
+pub fn generate(config: RoseyPublicConfig) -> Result<Vec<RoseyWarning>, RoseyError> {
+    let options = RoseyOptions::from_config(config, &RoseyCommand::Generate)?;
+    let mut generator = RoseyGenerator::from(options);
+    generator.run()?;
+    Ok(generator.warnings.take())
+}
+
+// Returns the problems Rosey worked around, such as invalid maximum lengths
+pub fn build(config: RoseyPublicConfig) -> Result<Vec<RoseyWarning>, RoseyError> {
+    let options = RoseyOptions::from_config(config, &RoseyCommand::Build)?;
+    let mut builder = RoseyBuilder::from(options);
+    builder.build()?;
+    Ok(builder.warnings.take())
+}
+
+pub fn check(config: RoseyPublicConfig) -> Result<(), RoseyError> {
+    let options = RoseyOptions::from_config(config, &RoseyCommand::Check)?;
+    RoseyChecker::from(options).run()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct RoseyTranslationEntry {
     pub original: Option<String>,
     pub value: Option<String>,
     pub pages: Option<BTreeMap<String, u32>>,
     pub total: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plurals: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_original: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<RoseyTranslationContext>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maxlength: Option<u32>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub machine_translated: bool,
+}
+
+// Where a key is used on the page, recorded by `rosey generate --context`
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RoseyTranslationContext {
+    pub element: String,
+    pub selector: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 impl RoseyTranslationEntry {
@@ -184,12 +469,12 @@ impl RoseyTranslationEntry {
             original: Some(original),
             pages: Some(BTreeMap::default()),
             total: Some(0),
-            value: None,
+            ..Default::default()
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum RoseyTranslation {
     V1(BTreeMap<String, String>),
@@ -244,6 +529,110 @@ impl RoseyTranslation {
         }
     }
 
+    pub fn get_plural(&self, key: &str, locale: &str, count: &str) -> Option<String> {
+        let entry = match self {
+            RoseyTranslation::V1(keys) => {
+                return keys.get(key).map(|value| value.replace("{count}", count))
+            }
+            RoseyTranslation::V2(keys) => keys.get(key)?,
+        };
+
+        let form = entry.plurals.as_ref().and_then(|plurals| {
+            let category = plurals::plural_category(locale, count);
+            [category, "other"]
+                .iter()
+                .filter_map(|category| plurals.get(*category))
+                .find(|form| !form.is_empty())
+        });
+
+        form.or(entry.value.as_ref())
+            .map(|value| value.replace("{count}", count))
+    }
+
+    pub fn insert_plurals(&mut self, key: &str, value: &str, locale: &str, count: &str) {
+        if let RoseyTranslation::V2(keys) = self {
+            if let Some(entry) = keys.get_mut(key) {
+                let plurals = entry.plurals.get_or_insert_with(BTreeMap::default);
+                for category in plurals::plural_categories(locale) {
+                    plurals.entry(category.to_string()).or_default();
+                }
+                if let Ok(count_pattern) =
+                    Regex::new(&format!(r"\b{}\b", regex::escape(count.trim())))
+                {
+                    let category = plurals::plural_category(locale, count);
+                    let form = plurals.entry(category.to_string()).or_default();
+                    if form.is_empty() {
+                        *form = count_pattern.replace(value, "{count}").into_owned();
+                    }
+                }
+            }
+        }
+    }
+
+    // Keys used in more than one place keep the context of their first use
+    pub fn insert_context(&mut self, key: &str, context: &RoseyTranslationContext) {
+        if let RoseyTranslation::V2(keys) = self {
+            if let Some(entry) = keys.get_mut(key) {
+                entry.context.get_or_insert_with(|| context.clone());
+            }
+        }
+    }
+
+    // Keys used in more than one place keep the strictest limit
+    pub fn insert_max_length(&mut self, key: &str, max_length: u32) {
+        if let RoseyTranslation::V2(keys) = self {
+            if let Some(entry) = keys.get_mut(key) {
+                entry.maxlength = Some(entry.maxlength.map_or(max_length, |m| m.min(max_length)));
+            }
+        }
+    }
+
+    pub fn merge_fallback(&mut self, fallback: &RoseyTranslation) {
+        match self {
+            RoseyTranslation::V1(keys) => {
+                for (key, entry) in fallback.normalize() {
+                    if keys.get(&key).is_none_or(String::is_empty) {
+                        if let Some(value) = entry.value {
+                            keys.insert(key, value);
+                        }
+                    }
+                }
+            }
+            RoseyTranslation::V2(keys) => {
+                for (key, fallback_entry) in fallback.normalize() {
+                    let entry = keys.entry(key).or_insert_with(|| RoseyTranslationEntry {
+                        original: fallback_entry.original.clone(),
+                        ..Default::default()
+                    });
+                    if entry.value.as_deref().is_none_or(str::is_empty) {
+                        entry.value = fallback_entry.value;
+                        if entry.plurals.is_none() {
+                            entry.plurals = fallback_entry.plurals;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Translations below the minimum review status are dropped, so that the
+    // original text (or a fallback locale) is used in their place.
+    // Version 1 locales have no statuses and are left as they are.
+    pub fn remove_unreviewed(&mut self, min_status: &str) {
+        let Some(min_rank) = review_rank(min_status) else {
+            return;
+        };
+        if let RoseyTranslation::V2(keys) = self {
+            for entry in keys.values_mut() {
+                let rank = entry.status.as_deref().and_then(review_rank).unwrap_or(0);
+                if rank < min_rank {
+                    entry.value = None;
+                    entry.plurals = None;
+                }
+            }
+        }
+    }
+
     pub fn len(&self) -> usize {
         match self {
             RoseyTranslation::V1(keys) => keys.len(),
@@ -275,9 +664,7 @@ impl RoseyTranslation {
                         key.clone(),
                         RoseyTranslationEntry {
                             value: Some(value.clone()),
-                            original: None,
-                            total: None,
-                            pages: None,
+                            ..Default::default()
                         },
                     )
                 })
@@ -323,7 +710,7 @@ impl RoseyLocale {
         RoseyLocale {
             version,
             keys: match version {
-                2 => RoseyTranslation::V2(BTreeMap::default()),
+                2 | 3 => RoseyTranslation::V2(BTreeMap::default()),
                 1 => RoseyTranslation::V1(BTreeMap::default()),
                 _ => unreachable!("Unsupported version"),
             },
@@ -340,13 +727,112 @@ impl RoseyLocale {
 
     pub fn output(&mut self, version: u8) -> String {
         match version {
-            2 => serde_json::to_string_pretty(self).unwrap(),
+            2 | 3 => serde_json::to_string_pretty(self).unwrap(),
             1 => serde_json::to_string_pretty(&self.keys).unwrap(),
             _ => unreachable!(),
         }
     }
 }
 
+// The maximum length of the text in an element, from a `data-rosey-maxlength`
+// attribute or the configured default for the element type
+pub fn element_max_length(
+    element: &kuchiki::ElementData,
+    tag: &str,
+    defaults: &BTreeMap<String, u32>,
+) -> Result<Option<u32>, String> {
+    let attribute = format!("{tag}-maxlength");
+    match element.attributes.borrow().get(attribute.as_str()) {
+        Some(max_length) => parse_max_length(element, &attribute, max_length).map(Some),
+        None => Ok(defaults.get(&*element.name.local).copied()),
+    }
+}
+
+// The maximum length of a translated attribute, which is only limited by its
+// own `data-rosey-maxlength-<attr>` attribute
+pub fn attribute_max_length(
+    element: &kuchiki::ElementData,
+    tag: &str,
+    attr: &str,
+) -> Result<Option<u32>, String> {
+    let attribute = format!("{tag}-maxlength-{attr}");
+    element
+        .attributes
+        .borrow()
+        .get(attribute.as_str())
+        .map(|max_length| parse_max_length(element, &attribute, max_length))
+        .transpose()
+}
+
+fn parse_max_length(
+    element: &kuchiki::ElementData,
+    attribute: &str,
+    max_length: &str,
+) -> Result<u32, String> {
+    max_length.trim().parse().map_err(|_| {
+        format!(
+            "ignoring {attribute}=\"{max_length}\" on <{}>, as it isn't a whole number of characters",
+            element.name.local
+        )
+    })
+}
+
+// The arguments used to template a message, from a `data-rosey-args` attribute
+// holding a JSON object
+pub fn element_message_args(
+    element: &kuchiki::ElementData,
+    tag: &str,
+    key: &str,
+) -> Result<Option<serde_json::Map<String, serde_json::Value>>, String> {
+    let attribute = format!("{tag}-args");
+    element
+        .attributes
+        .borrow()
+        .get(attribute.as_str())
+        .map(|args| {
+            serde_json::from_str(args).map_err(|e| {
+                format!("ignoring {attribute} for \"{key}\", as it isn't a JSON object: {e}")
+            })
+        })
+        .transpose()
+}
+
+// The number of characters a reader sees, ignoring any HTML tags
+pub fn text_length(value: &str) -> usize {
+    let mut length = 0;
+    let mut in_tag = false;
+    for c in value.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => length += 1,
+            _ => {}
+        }
+    }
+    length
+}
+
+fn review_rank(status: &str) -> Option<usize> {
+    SUPPORTED_REVIEW_STATUSES.iter().position(|s| *s == status)
+}
+
+// Returns the locale followed by each locale it falls back to, depth first.
+pub fn fallback_chain(fallbacks: &BTreeMap<String, Vec<String>>, locale: &str) -> Vec<String> {
+    fn visit(fallbacks: &BTreeMap<String, Vec<String>>, locale: &str, chain: &mut Vec<String>) {
+        if chain.iter().any(|l| l == locale) {
+            return;
+        }
+        chain.push(locale.to_string());
+        for fallback in fallbacks.get(locale).into_iter().flatten() {
+            visit(fallbacks, fallback, chain);
+        }
+    }
+
+    let mut chain = vec![];
+    visit(fallbacks, locale, &mut chain);
+    chain
+}
+
 pub fn inline_templates(dom: &kuchiki::NodeRef) {
     dom.inclusive_descendants().for_each(|node| {
         if let Some(kuchiki::ElementData {
@@ -0,0 +1,137 @@
+use fixed_decimal::Decimal;
+use icu_datetime::{
+    fieldsets::{T, YMD, YMDE},
+    input::{Date, Time},
+    options::Length,
+    DateTimeFormatter, NoCalendarFormatter,
+};
+use icu_decimal::DecimalFormatter;
+use icu_experimental::dimension::{
+    currency::{formatter::CurrencyFormatter, CurrencyType},
+    percent::formatter::PercentFormatter,
+};
+use icu_locale_core::Locale;
+
+fn locale_id(locale: &str) -> String {
+    locale.replace('_', "-")
+}
+
+fn parse_locale(locale: &str) -> Locale {
+    locale_id(locale).parse().unwrap_or(Locale::UNKNOWN)
+}
+
+pub fn format_number(locale: &str, value: &str, style: Option<&str>) -> Option<String> {
+    let mut decimal = value.trim().parse::<Decimal>().ok()?;
+    let locale = parse_locale(locale);
+    match style {
+        Some("percent") => {
+            decimal.multiply_pow10(2);
+            decimal.round(0);
+            decimal.trim_start();
+            let formatter = PercentFormatter::try_new(locale.into(), Default::default()).ok()?;
+            Some(formatter.format(&decimal).to_string())
+        }
+        style => {
+            if style == Some("integer") {
+                decimal.round(0);
+            }
+            let formatter = DecimalFormatter::try_new(locale.into(), Default::default()).ok()?;
+            Some(formatter.format(&decimal).to_string())
+        }
+    }
+}
+
+// Uses the currency's CLDR symbol, pattern and number of decimal places
+pub fn format_currency(locale: &str, value: &str, code: &str) -> Option<String> {
+    let currency = CurrencyType::try_from_str(code.trim()).ok()?;
+    let decimal = value.trim().parse::<Decimal>().ok()?;
+    let formatter = CurrencyFormatter::try_new_symbol(
+        parse_locale(locale).into(),
+        currency,
+        Default::default(),
+    )
+    .ok()?;
+    let formatted = formatter.format_fixed_decimal(&decimal).to_string();
+    Some(formatted)
+}
+
+fn date_length(style: Option<&str>) -> Length {
+    match style {
+        Some("short") => Length::Short,
+        Some("long") => Length::Long,
+        _ => Length::Medium,
+    }
+}
+
+// Accepts ISO 8601 dates such as `2024-03-05` or `2024-03-05T14:30:00`. Any UTC
+// offset is ignored, so times are formatted as they were written
+fn parse_iso_datetime(value: &str) -> Option<(i32, u8, u8, u8, u8, u8)> {
+    let value = value.trim();
+    let (date, time) = match value.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    let mut date_parts = date.splitn(3, '-');
+    let year = date_parts.next()?.parse().ok()?;
+    let month = date_parts.next()?.parse().ok()?;
+    let day = date_parts.next()?.parse().ok()?;
+
+    let (mut hour, mut minute, mut second) = (0, 0, 0);
+    if let Some(time) = time {
+        let time = time.trim_end_matches('Z');
+        let time = time.split(['+', '-', '.']).next().unwrap_or(time);
+        let mut time_parts = time.splitn(3, ':');
+        hour = time_parts.next()?.parse().ok()?;
+        minute = time_parts.next().unwrap_or("0").parse().ok()?;
+        second = time_parts.next().unwrap_or("0").parse().ok()?;
+    }
+
+    Some((year, month, day, hour, minute, second))
+}
+
+// Full dates are long dates with the day of the week
+pub fn format_date(locale: &str, value: &str, style: Option<&str>) -> Option<String> {
+    let (year, month, day, ..) = parse_iso_datetime(value)?;
+    let locale = locale_id(locale).parse::<Locale>().ok()?;
+    let date = Date::try_new_iso(year, month, day).ok()?;
+    let formatted = match style {
+        Some("full") => DateTimeFormatter::try_new(locale.into(), YMDE::long())
+            .ok()?
+            .format(&date)
+            .to_string(),
+        style => DateTimeFormatter::try_new(locale.into(), YMD::for_length(date_length(style)))
+            .ok()?
+            .format(&date)
+            .to_string(),
+    };
+    Some(formatted)
+}
+
+// Short times have hours and minutes, and longer times add the seconds
+pub fn format_time(locale: &str, value: &str, style: Option<&str>) -> Option<String> {
+    let (_, _, _, hour, minute, second) = parse_iso_datetime(value)?;
+    let locale = locale_id(locale).parse::<Locale>().ok()?;
+    let time = Time::try_new(hour, minute, second, 0).ok()?;
+    let field_set = match style {
+        Some("short") => T::hm(),
+        _ => T::hms(),
+    };
+    let formatter = NoCalendarFormatter::try_new(locale.into(), field_set).ok()?;
+    Some(formatter.format(&time).to_string())
+}
+
+// Formats a value for a `data-rosey-format` attribute such as `date:long` or `currency:EUR`
+pub fn format_value(locale: &str, format: &str, value: &str) -> Option<String> {
+    let (kind, style) = match format.split_once(':') {
+        Some((kind, style)) => (kind.trim(), Some(style.trim())),
+        None => (format.trim(), None),
+    };
+    match kind {
+        "number" => format_number(locale, value, style),
+        "currency" => format_currency(locale, value, style?),
+        "date" => format_date(locale, value, style),
+        "time" => format_time(locale, value, style),
+        _ => None,
+    }
+}
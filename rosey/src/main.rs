@@ -33,9 +33,9 @@ async fn main() {
                     Arg::new("version")
                         .short('v')
                         .long("version")
-                        .value_parser(["1", "2"])
+                        .value_parser(["1", "2", "3"])
                         .value_name("VERSION")
-                        .help(&format!(
+                        .help(format!(
                             "The Rosey locale version to generate and build from. \n ─ Defaults to '{}'",
                             example_defaults.version
                         )),
@@ -44,7 +44,7 @@ async fn main() {
                     Arg::new("tag")
                         .long("tag")
                         .value_name("ATTRIBUTE")
-                        .help(&format!(
+                        .help(format!(
                             "The HTML attribute that Rosey should read from. \n ─ Defaults to '{}'",
                             example_defaults.tag
                         )),
@@ -53,7 +53,7 @@ async fn main() {
                     Arg::new("base")
                         .long("base")
                         .value_name("PATH")
-                        .help(&format!(
+                        .help(format!(
                             "The file to generate the Rosey base locale file to. \n ─ Defaults to '{}'",
                             example_defaults.base.display()
                         )),
@@ -62,7 +62,7 @@ async fn main() {
                     Arg::new("base-urls")
                         .long("base-urls")
                         .value_name("PATH")
-                        .help(&format!(
+                        .help(format!(
                             "The file to generate the Rosey base URLs file to. \n ─ Defaults to '{}'",
                             example_defaults.base_urls.display()
                         )),
@@ -71,11 +71,23 @@ async fn main() {
                     Arg::new("separator")
                         .long("separator")
                         .value_name("CHAR")
-                        .help(&format!(
+                        .help(format!(
                             "The separator to use between Rosey namespaces when generating keys. \n ─ Defaults to '{}'",
                             example_defaults.separator
                         )),
                 )
+                .arg(
+                    Arg::new("pot")
+                        .long("pot")
+                        .action(ArgAction::SetTrue)
+                        .help("Also generate a gettext POT template next to the Rosey base locale file"),
+                )
+                .arg(
+                    Arg::new("context")
+                        .long("context")
+                        .action(ArgAction::SetTrue)
+                        .help("Record where each key is used on the page, for translators, in the Rosey base locale file"),
+                )
                 .arg(
                     Arg::new("config-dump")
                         .long("config-dump")
@@ -103,7 +115,7 @@ async fn main() {
                     Arg::new("tag")
                         .long("tag")
                         .value_name("ATTRIBUTE")
-                        .help(&format!(
+                        .help(format!(
                             "The HTML attribute that Rosey should read from. \n ─ Defaults to '{}'",
                             example_defaults.tag
                         )),
@@ -112,7 +124,7 @@ async fn main() {
                     Arg::new("locales")
                         .long("locales")
                         .value_name("PATH")
-                        .help(&format!(
+                        .help(format!(
                             "The directory to read translated Rosey locale files from. \n ─ Defaults to '{}'",
                             example_defaults.locales.display()
                         )),
@@ -121,7 +133,7 @@ async fn main() {
                     Arg::new("default-language")
                         .long("default-language")
                         .value_name("LANG")
-                        .help(&format!(
+                        .help(format!(
                             "The default language for the site (i.e. the language of 'base.json'). \n ─ Defaults to '{}'",
                             example_defaults.default_language
                         )),
@@ -130,7 +142,7 @@ async fn main() {
                     Arg::new("exclusions")
                         .long("exclusions")
                         .value_name("REGEX")
-                        .help(&format!(
+                        .help(format!(
                             "A regular expression used to determine which files not to copy as assets. \n ─ Defaults to '{}'",
                             example_defaults.exclusions
                         )),
@@ -139,7 +151,7 @@ async fn main() {
                     Arg::new("separator")
                         .long("separator")
                         .value_name("CHAR")
-                        .help(&format!(
+                        .help(format!(
                             "The separator to use between Rosey namespaces when generating keys. \n ─ Defaults to '{}'",
                             example_defaults.separator
                         )),
@@ -169,11 +181,40 @@ async fn main() {
                         .value_name("PATH")
                         .help("Path to a redirect template that Rosey should use instead of the default file"),
                 )
+                .arg(
+                    Arg::new("redirects")
+                        .long("redirects")
+                        .value_name("FORMATS")
+                        .value_delimiter(',')
+                        .value_parser(["netlify", "vercel", "nginx"])
+                        .action(ArgAction::Append)
+                        .conflicts_with("default-language-at-root")
+                        .help("Write server-side language redirect rules for the given platforms, e.g. 'netlify,nginx'"),
+                )
+                .arg(
+                    Arg::new("strict")
+                        .long("strict")
+                        .action(ArgAction::SetTrue)
+                        .help("Fail the build when a translation is longer than the maximum length of its element"),
+                )
+                .arg(
+                    Arg::new("min-status")
+                        .long("min-status")
+                        .value_name("STATUS")
+                        .value_parser(["draft", "needs-review", "approved"])
+                        .help("Only use translations with at least this review status, falling back to the original text for others"),
+                )
+                .arg(
+                    Arg::new("skip-redirect-pages")
+                        .long("skip-redirect-pages")
+                        .action(ArgAction::SetTrue)
+                        .help("Don't write HTML redirect pages, e.g. when redirecting with --redirects instead"),
+                )
                 .arg(
                     Arg::new("wrap")
                         .long("wrap")
                         .action(ArgAction::Append)
-                        .help("For languages without significant whitespace, add spans around detected words to break lines cleanly")
+                        .help("For the given languages or scripts, such as ja or Khmr, add spans around detected words to break lines cleanly")
                         .num_args(0..)
                 )
                 .arg(
@@ -182,12 +223,40 @@ async fn main() {
                         .value_name("CLASS")
                         .help("When wrapping languages, use the given classname instead of inline styles")
                 )
+                .arg(
+                    Arg::new("wrap-mode")
+                        .long("wrap-mode")
+                        .value_name("MODE")
+                        .value_parser(["span", "wbr", "zwsp"])
+                        .help("How to wrap languages: 'span' wraps each word in a span, 'wbr' and 'zwsp' mark the breaks between words with <wbr> or a zero-width space [default: span]")
+                )
                 .arg(
                     Arg::new("serve")
                         .long("serve")
                         .action(ArgAction::SetTrue)
                         .help("Runs a local webserver on the dest folder after a successful build. Useful for local development")
                 )
+                .arg(
+                    Arg::new("sitemap")
+                        .long("sitemap")
+                        .action(ArgAction::SetTrue)
+                        .help("Write a sitemap.xml with alternate links for every locale, or rewrite the sitemap.xml from the source directory")
+                )
+                .arg(
+                    Arg::new("incremental")
+                        .long("incremental")
+                        .action(ArgAction::SetTrue)
+                        .help("Only rebuild pages and assets that have changed since the last incremental build")
+                )
+                .arg(
+                    Arg::new("build-cache")
+                        .long("build-cache")
+                        .value_name("FILE")
+                        .help(format!(
+                            "The path to read and write the incremental build manifest. \n ─ Defaults to '{}'",
+                            example_defaults.build_cache.display()
+                        )),
+                )
                 .arg(
                     Arg::new("config-dump")
                         .long("config-dump")
@@ -201,7 +270,7 @@ async fn main() {
                     Arg::new("locales")
                         .long("locales")
                         .value_name("PATH")
-                        .help(&format!(
+                        .help(format!(
                             "The directory to read translated Rosey locale files from. \n ─ Defaults to '{}'",
                             example_defaults.locales.display()
                         )),
@@ -210,7 +279,7 @@ async fn main() {
                     Arg::new("base")
                         .long("base")
                         .value_name("PATH")
-                        .help(&format!(
+                        .help(format!(
                             "The path to a Rosey base locale file. \n ─ Defaults to '{}'",
                             example_defaults.base.display()
                         )),
@@ -220,12 +289,260 @@ async fn main() {
                         .short('v')
                         .long("version")
                         .value_name("VERSION")
-                        .value_parser(["1", "2"])
-                        .help(&format!(
+                        .value_parser(["1", "2", "3"])
+                        .help(format!(
                             "The Rosey locale version to generate and build from. \n ─ Defaults to '{}'",
                             example_defaults.version
                         )),
                 )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .value_parser(["json", "junit", "markdown"])
+                        .help(format!(
+                            "The format of the checks file to output next to the base locale file. \n ─ Defaults to '{}'",
+                            example_defaults.check_format
+                        )),
+                )
+                .arg(
+                    Arg::new("fail-on")
+                        .long("fail-on")
+                        .value_name("STATES")
+                        .value_delimiter(',')
+                        .value_parser(["outdated", "mismatched", "missing", "unused", "overlong"])
+                        .action(ArgAction::Append)
+                        .help("Exit with a non-zero status if any locale has translations in one of these states, e.g. 'missing,outdated'"),
+                )
+                .arg(
+                    Arg::new("config-dump")
+                        .long("config-dump")
+                        .action(ArgAction::SetTrue)
+                        .help("Print all resolved configuration and exit without taking any action"),
+                ),
+        )
+        .subcommand(
+            Command::new("suggest")
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The path to a Rosey base locale file. \n ─ Defaults to '{}'",
+                            example_defaults.base.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("locales")
+                        .long("locales")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The directory of translated Rosey locale files to fill with suggestions. \n ─ Defaults to '{}'",
+                            example_defaults.locales.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("memory")
+                        .long("memory")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The translation memory file to read and update. \n ─ Defaults to '{}'",
+                            example_defaults.memory.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("threshold")
+                        .long("threshold")
+                        .value_name("PERCENT")
+                        .help(format!(
+                            "The minimum similarity for a fuzzy suggestion, from 0 to 100. \n ─ Defaults to '{}'",
+                            example_defaults.suggest_threshold
+                        )),
+                )
+                .arg(
+                    Arg::new("config-dump")
+                        .long("config-dump")
+                        .action(ArgAction::SetTrue)
+                        .help("Print all resolved configuration and exit without taking any action"),
+                ),
+        )
+        .subcommand(
+            Command::new("sync")
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The path to a Rosey base locale file. \n ─ Defaults to '{}'",
+                            example_defaults.base.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("locales")
+                        .long("locales")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The directory of translated Rosey locale files to update. \n ─ Defaults to '{}'",
+                            example_defaults.locales.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("prune-unused")
+                        .long("prune-unused")
+                        .action(ArgAction::SetTrue)
+                        .help("Remove keys that are no longer in the base locale file from each locale file"),
+                )
+                .arg(
+                    Arg::new("config-dump")
+                        .long("config-dump")
+                        .action(ArgAction::SetTrue)
+                        .help("Print all resolved configuration and exit without taking any action"),
+                ),
+        )
+        .subcommand(
+            Command::new("translate")
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The path to a Rosey base locale file. \n ─ Defaults to '{}'",
+                            example_defaults.base.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("locales")
+                        .long("locales")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The directory of translated Rosey locale files. \n ─ Defaults to '{}'",
+                            example_defaults.locales.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("locale")
+                        .long("locale")
+                        .value_name("LOCALE")
+                        .help("The locale to fill with machine translations, e.g. 'de'"),
+                )
+                .arg(
+                    Arg::new("provider")
+                        .long("provider")
+                        .value_name("NAME")
+                        .help("The name of a translation provider from the providers option to translate with"),
+                )
+                .arg(
+                    Arg::new("config-dump")
+                        .long("config-dump")
+                        .action(ArgAction::SetTrue)
+                        .help("Print all resolved configuration and exit without taking any action"),
+                ),
+        )
+        .subcommand(
+            Command::new("export")
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The path to a Rosey base locale file. \n ─ Defaults to '{}'",
+                            example_defaults.base.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("locales")
+                        .long("locales")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The directory to read translated Rosey locale files from. \n ─ Defaults to '{}'",
+                            example_defaults.locales.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("exports")
+                        .long("exports")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The directory to write exported translation files to. \n ─ Defaults to '{}'",
+                            example_defaults.exports.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .value_parser(["xliff"])
+                        .help(format!(
+                            "The file format to export translations to. \n ─ Defaults to '{}'",
+                            example_defaults.export_format
+                        )),
+                )
+                .arg(
+                    Arg::new("xliff-version")
+                        .long("xliff-version")
+                        .value_name("VERSION")
+                        .value_parser(["1.2", "2.0"])
+                        .help(format!(
+                            "The XLIFF version to export. \n ─ Defaults to '{}'",
+                            example_defaults.xliff_version
+                        )),
+                )
+                .arg(
+                    Arg::new("default-language")
+                        .long("default-language")
+                        .value_name("LANG")
+                        .help(format!(
+                            "The default language for the site (i.e. the language of 'base.json'). \n ─ Defaults to '{}'",
+                            example_defaults.default_language
+                        )),
+                )
+                .arg(
+                    Arg::new("config-dump")
+                        .long("config-dump")
+                        .action(ArgAction::SetTrue)
+                        .help("Print all resolved configuration and exit without taking any action"),
+                ),
+        )
+        .subcommand(
+            Command::new("import")
+                .arg(
+                    Arg::new("base")
+                        .long("base")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The path to a Rosey base locale file. \n ─ Defaults to '{}'",
+                            example_defaults.base.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("locales")
+                        .long("locales")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The directory to write translated Rosey locale files to. \n ─ Defaults to '{}'",
+                            example_defaults.locales.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("exports")
+                        .long("exports")
+                        .value_name("PATH")
+                        .help(format!(
+                            "The directory to read translation files from. \n ─ Defaults to '{}'",
+                            example_defaults.exports.display()
+                        )),
+                )
+                .arg(
+                    Arg::new("version")
+                        .short('v')
+                        .long("version")
+                        .value_name("VERSION")
+                        .value_parser(["1", "2", "3"])
+                        .help(format!(
+                            "The Rosey locale version to create new locale files with. \n ─ Defaults to '{}'",
+                            example_defaults.version
+                        )),
+                )
                 .arg(
                     Arg::new("config-dump")
                         .long("config-dump")
@@ -246,7 +563,10 @@ async fn main() {
     });
 
     let options = RoseyOptions::load_with_flags(submatches, &subcommand);
-    options.run(subcommand).await;
+    if let Err(e) = options.run(subcommand).await {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
 
     let duration = start.elapsed();
     println!(
@@ -0,0 +1,167 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+static FRONT_MATTER_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(\s*[\w-]+:[ \t]*)(.*?)[ \t]+#[ \t]*(rosey(?:-ns|:\S+)(?:\|rosey(?:-ns|:\S+))*)[ \t]*$",
+    )
+    .unwrap()
+});
+
+static MARKER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?s)<!--\s*rosey-ns:(\S+?)\s*-->(?:\r?\n)?|<!--\s*rosey:(\S+?)\s*-->(.*?)<!--\s*/rosey\s*-->",
+    )
+    .unwrap()
+});
+
+// Markdown and plain text files are tagged with Rosey markers inline:
+//
+//   ---
+//   title: About us # rosey:title
+//   ---
+//   <!-- rosey-ns:about -->
+//   <!-- rosey:intro -->
+//   We make things.
+//   <!-- /rosey -->
+//
+// Translated copies have the values replaced and the markers removed.
+fn walk_markdown(content: &str, mut translate: impl FnMut(&str, &str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut namespace = String::new();
+
+    let body = match front_matter(content) {
+        Some((start, end)) => {
+            output.push_str(&content[..start]);
+            for line in content[start..end].split_inclusive('\n') {
+                let text = line.trim_end_matches(['\r', '\n']);
+                let Some(captures) = FRONT_MATTER_FIELD.captures(text) else {
+                    output.push_str(line);
+                    continue;
+                };
+
+                let raw = &captures[2];
+                let value = yaml_scalar(raw);
+                let mut translated = None;
+                for part in captures[3].split('|') {
+                    if part == "rosey-ns" {
+                        namespace.push_str(&value.to_lowercase());
+                        namespace.push('.');
+                    } else if let Some(key) = part.strip_prefix("rosey:") {
+                        translated = translate(&format!("{namespace}{key}"), &value);
+                    }
+                }
+
+                output.push_str(&captures[1]);
+                match translated {
+                    Some(translated) => output.push_str(&yaml_quote(raw, &translated)),
+                    None => output.push_str(raw),
+                }
+                output.push_str(&line[text.len()..]);
+            }
+            &content[end..]
+        }
+        None => content,
+    };
+
+    let front_matter_namespace = namespace.clone();
+    let mut last = 0;
+    for captures in MARKER.captures_iter(body) {
+        let matched = captures.get(0).unwrap();
+        output.push_str(&body[last..matched.start()]);
+        last = matched.end();
+
+        if let Some(block_namespace) = captures.get(1) {
+            namespace = format!("{front_matter_namespace}{}.", block_namespace.as_str());
+            continue;
+        }
+
+        let inner = &captures[3];
+        let value = inner.trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        // Markers on their own lines take their line break with them
+        let leading = &inner[..inner.len() - inner.trim_start().len()];
+        let leading = leading
+            .strip_prefix("\r\n")
+            .or_else(|| leading.strip_prefix('\n'))
+            .unwrap_or(leading);
+        let trailing = &inner[inner.trim_end().len()..];
+        let trailing = trailing
+            .strip_suffix("\r\n")
+            .or_else(|| trailing.strip_suffix('\n'))
+            .unwrap_or(trailing);
+
+        output.push_str(leading);
+        match translate(&format!("{namespace}{}", &captures[2]), value) {
+            Some(translated) => output.push_str(&translated),
+            None => output.push_str(value),
+        }
+        output.push_str(trailing);
+    }
+    output.push_str(&body[last..]);
+
+    output
+}
+
+// The byte range of the front matter fields, between the `---` fences
+fn front_matter(content: &str) -> Option<(usize, usize)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let start = content.len() - rest.len();
+
+    let mut end = start;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((start, end));
+        }
+        end += line.len();
+    }
+    None
+}
+
+fn yaml_scalar(raw: &str) -> String {
+    if raw.len() >= 2 && raw.starts_with('"') && raw.ends_with('"') {
+        return serde_json::from_str(raw).unwrap_or_else(|_| raw[1..raw.len() - 1].to_string());
+    }
+    if raw.len() >= 2 && raw.starts_with('\'') && raw.ends_with('\'') {
+        return raw[1..raw.len() - 1].replace("''", "'");
+    }
+    raw.to_string()
+}
+
+fn yaml_quote(raw: &str, value: &str) -> String {
+    if raw.starts_with('\'') {
+        return format!("'{}'", value.replace('\'', "''"));
+    }
+
+    let needs_quotes = raw.starts_with('"')
+        || value.is_empty()
+        || value != value.trim()
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.contains('\n')
+        || value.starts_with(|c: char| "!&*-?{}[]|>'\"%@`#,".contains(c));
+    if needs_quotes {
+        serde_json::to_string(value).unwrap()
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn markdown_keys(content: &str) -> Vec<(String, String)> {
+    let mut keys = vec![];
+    walk_markdown(content, |key, value| {
+        keys.push((key.to_string(), value.to_string()));
+        None
+    });
+    keys
+}
+
+pub fn translate_markdown(content: &str, translate: impl Fn(&str) -> Option<String>) -> String {
+    walk_markdown(content, |key, _| translate(key))
+}
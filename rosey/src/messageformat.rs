@@ -0,0 +1,353 @@
+use std::{collections::BTreeSet, iter::Peekable, str::Chars};
+
+use regex::{NoExpand, Regex};
+use serde_json::{Map, Value};
+
+use crate::{
+    locale_format::{format_date, format_number, format_time},
+    plurals::{ordinal_category, plural_category},
+};
+
+#[derive(Debug, PartialEq)]
+enum MessagePart {
+    Text(String),
+    Hash,
+    Argument(String),
+    Formatted {
+        name: String,
+        kind: String,
+        style: Option<String>,
+    },
+    Plural {
+        name: String,
+        ordinal: bool,
+        offset: f64,
+        arms: Vec<(String, Vec<MessagePart>)>,
+    },
+    Select {
+        name: String,
+        arms: Vec<(String, Vec<MessagePart>)>,
+    },
+}
+
+struct MessageParser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl MessageParser<'_> {
+    fn parse(message: &str) -> Option<Vec<MessagePart>> {
+        let mut parser = MessageParser {
+            chars: message.chars().peekable(),
+        };
+        let parts = parser.parse_message(false, false)?;
+        parser.chars.peek().is_none().then_some(parts)
+    }
+
+    fn parse_message(&mut self, nested: bool, in_plural: bool) -> Option<Vec<MessagePart>> {
+        let mut parts = vec![];
+        let mut text = String::new();
+
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                '}' if nested => break,
+                '}' => return None,
+                '{' => {
+                    self.chars.next();
+                    if !text.is_empty() {
+                        parts.push(MessagePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(self.parse_argument()?);
+                }
+                '#' if in_plural => {
+                    self.chars.next();
+                    if !text.is_empty() {
+                        parts.push(MessagePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(MessagePart::Hash);
+                }
+                '\'' => {
+                    self.chars.next();
+                    match self.chars.peek() {
+                        Some('\'') => {
+                            self.chars.next();
+                            text.push('\'');
+                        }
+                        // An apostrophe before a special character quotes
+                        // everything up to the next single apostrophe.
+                        Some('{' | '}' | '#') => {
+                            while let Some(c) = self.chars.next() {
+                                if c != '\'' {
+                                    text.push(c);
+                                } else if self.chars.peek() == Some(&'\'') {
+                                    self.chars.next();
+                                    text.push('\'');
+                                } else {
+                                    break;
+                                }
+                            }
+                        }
+                        _ => text.push('\''),
+                    }
+                }
+                _ => {
+                    self.chars.next();
+                    text.push(c);
+                }
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(MessagePart::Text(text));
+        }
+        Some(parts)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn parse_word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| !c.is_whitespace() && !matches!(c, ',' | '{' | '}'))
+        {
+            word.push(c);
+        }
+        word
+    }
+
+    fn expect(&mut self, expected: char) -> Option<()> {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&expected).map(|_| ())
+    }
+
+    fn parse_argument(&mut self) -> Option<MessagePart> {
+        self.skip_whitespace();
+        let name = self.parse_word();
+        if name.is_empty() {
+            return None;
+        }
+
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'}').is_some() {
+            return Some(MessagePart::Argument(name));
+        }
+        self.expect(',')?;
+        self.skip_whitespace();
+        let kind = self.parse_word();
+
+        match kind.as_str() {
+            "plural" | "selectordinal" | "select" => {
+                self.expect(',')?;
+                let in_plural = kind != "select";
+                let mut offset = 0.0;
+                let mut arms = vec![];
+                loop {
+                    self.skip_whitespace();
+                    if self.chars.next_if_eq(&'}').is_some() {
+                        break;
+                    }
+                    let selector = self.parse_word();
+                    if selector.is_empty() {
+                        return None;
+                    }
+                    if let Some(value) = selector.strip_prefix("offset:") {
+                        offset = value.parse().ok()?;
+                        continue;
+                    }
+                    self.expect('{')?;
+                    let message = self.parse_message(true, in_plural)?;
+                    self.expect('}')?;
+                    arms.push((selector, message));
+                }
+
+                if !arms.iter().any(|(selector, _)| selector == "other") {
+                    return None;
+                }
+                Some(match kind.as_str() {
+                    "select" => MessagePart::Select { name, arms },
+                    _ => MessagePart::Plural {
+                        name,
+                        ordinal: kind == "selectordinal",
+                        offset,
+                        arms,
+                    },
+                })
+            }
+            "number" | "date" | "time" => {
+                self.skip_whitespace();
+                let style = if self.chars.next_if_eq(&',').is_some() {
+                    let mut style = String::new();
+                    while let Some(c) = self.chars.next_if(|c| *c != '}') {
+                        style.push(c);
+                    }
+                    Some(style.trim().to_string())
+                } else {
+                    None
+                };
+                self.expect('}')?;
+                Some(MessagePart::Formatted { name, kind, style })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn argument_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn format_parts(
+    parts: &[MessagePart],
+    locale: &str,
+    args: &Map<String, Value>,
+    plural_value: Option<&str>,
+    output: &mut String,
+) {
+    for part in parts {
+        match part {
+            MessagePart::Text(text) => output.push_str(text),
+            MessagePart::Hash => match plural_value {
+                Some(value) => output
+                    .push_str(&format_number(locale, value, None).unwrap_or(value.to_string())),
+                None => output.push('#'),
+            },
+            MessagePart::Argument(name) => {
+                match args.get(name) {
+                    Some(Value::Number(value)) => {
+                        let value = value.to_string();
+                        output.push_str(&format_number(locale, &value, None).unwrap_or(value));
+                    }
+                    Some(value) => output.push_str(&argument_value(value).unwrap_or_default()),
+                    None => output.push_str(&format!("{{{name}}}")),
+                };
+            }
+            MessagePart::Formatted { name, kind, style } => {
+                let Some(value) = args.get(name).and_then(argument_value) else {
+                    output.push_str(&format!("{{{name}}}"));
+                    continue;
+                };
+                let style = style.as_deref();
+                let formatted = match kind.as_str() {
+                    "number" => format_number(locale, &value, style),
+                    "date" => format_date(locale, &value, style),
+                    "time" => format_time(locale, &value, style),
+                    _ => None,
+                };
+                output.push_str(&formatted.unwrap_or(value));
+            }
+            MessagePart::Plural {
+                name,
+                ordinal,
+                offset,
+                arms,
+            } => {
+                let value = args.get(name).and_then(argument_value);
+                let number = value.as_deref().and_then(|value| value.parse::<f64>().ok());
+                let Some((value, number)) = value.zip(number) else {
+                    output.push_str(&format!("{{{name}}}"));
+                    continue;
+                };
+
+                let adjusted = (number - offset).to_string();
+                let category = if *ordinal {
+                    ordinal_category(locale, &adjusted)
+                } else {
+                    plural_category(locale, &adjusted)
+                };
+                let arm = arms
+                    .iter()
+                    .find(|(selector, _)| {
+                        selector
+                            .strip_prefix('=')
+                            .and_then(|exact| exact.parse::<f64>().ok())
+                            .is_some_and(|exact| exact == number)
+                    })
+                    .or_else(|| arms.iter().find(|(selector, _)| selector == category))
+                    .or_else(|| arms.iter().find(|(selector, _)| selector == "other"));
+                if let Some((_, message)) = arm {
+                    let plural_value = if *offset == 0.0 { &value } else { &adjusted };
+                    format_parts(message, locale, args, Some(plural_value), output);
+                }
+            }
+            MessagePart::Select { name, arms } => {
+                let value = args.get(name).and_then(argument_value).unwrap_or_default();
+                let arm = arms
+                    .iter()
+                    .find(|(selector, _)| *selector == value)
+                    .or_else(|| arms.iter().find(|(selector, _)| selector == "other"));
+                if let Some((_, message)) = arm {
+                    format_parts(message, locale, args, plural_value, output);
+                }
+            }
+        }
+    }
+}
+
+pub fn format_message(message: &str, locale: &str, args: &Map<String, Value>) -> Option<String> {
+    let parts = MessageParser::parse(message)?;
+    let mut output = String::new();
+    format_parts(&parts, locale, args, None, &mut output);
+    Some(output)
+}
+
+fn collect_placeholders(parts: &[MessagePart], placeholders: &mut BTreeSet<String>) {
+    for part in parts {
+        match part {
+            MessagePart::Text(_) | MessagePart::Hash => {}
+            MessagePart::Argument(name) | MessagePart::Formatted { name, .. } => {
+                placeholders.insert(name.clone());
+            }
+            MessagePart::Plural { name, arms, .. } | MessagePart::Select { name, arms } => {
+                placeholders.insert(name.clone());
+                for (_, message) in arms {
+                    collect_placeholders(message, placeholders);
+                }
+            }
+        }
+    }
+}
+
+pub fn placeholders(message: &str) -> Option<BTreeSet<String>> {
+    let parts = MessageParser::parse(message)?;
+    let mut placeholders = BTreeSet::new();
+    collect_placeholders(&parts, &mut placeholders);
+    Some(placeholders)
+}
+
+// Turns rendered text such as `Hello Alice` into `Hello {name}`
+// by swapping the first occurrence of each argument value for its placeholder.
+pub fn message_template(value: &str, args: &Map<String, Value>) -> String {
+    args.iter().fold(value.to_string(), |message, (name, arg)| {
+        let Some(arg) = argument_value(arg) else {
+            return message;
+        };
+        let arg = arg.trim();
+        if arg.is_empty() {
+            return message;
+        }
+
+        let boundary = |c: Option<char>| match c {
+            Some(c) if c.is_alphanumeric() => r"\b",
+            _ => "",
+        };
+        let pattern = format!(
+            "{}{}{}",
+            boundary(arg.chars().next()),
+            regex::escape(arg),
+            boundary(arg.chars().last())
+        );
+        match Regex::new(&pattern) {
+            Ok(pattern) => pattern
+                .replace(&message, NoExpand(&format!("{{{name}}}")))
+                .into_owned(),
+            Err(_) => message,
+        }
+    })
+}
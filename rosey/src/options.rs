@@ -1,4 +1,4 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
 
 use anyhow::{Context, Error};
 use figment::{
@@ -23,12 +23,54 @@ pub struct RoseyPublicConfig {
     pub images_source: Option<PathBuf>,
     pub default_language: String,
     pub redirect_page: Option<PathBuf>,
+    pub redirects: Option<Vec<String>>,
+    pub skip_redirect_pages: bool,
     pub default_language_at_root: bool,
     pub wrap: Option<Vec<String>>,
     pub wrap_class: Option<String>,
+    pub wrap_mode: String,
+    pub exports: PathBuf,
+    pub export_format: String,
+    pub xliff_version: String,
+    pub pot: bool,
+    pub context: bool,
+    pub check_format: String,
+    pub fail_on: Option<Vec<String>>,
+    pub sitemap: bool,
+    pub incremental: bool,
+    pub build_cache: PathBuf,
+    pub fallbacks: BTreeMap<String, Vec<String>>,
+    pub directions: BTreeMap<String, String>,
+    pub min_status: Option<String>,
+    pub max_lengths: BTreeMap<String, u32>,
+    pub strict: bool,
+    pub memory: PathBuf,
+    pub prune_unused: bool,
+    pub suggest_threshold: u8,
+    pub translate_locale: Option<String>,
+    pub provider: Option<String>,
+    pub providers: BTreeMap<String, RoseyProviderConfig>,
     pub verbose: bool,
 }
 
+// A machine translation service for `rosey translate`, chosen with `--provider <name>`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RoseyProviderConfig {
+    Http {
+        url: String,
+        #[serde(default)]
+        api_key: Option<String>,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    Command {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
 impl Default for RoseyPublicConfig {
     fn default() -> RoseyPublicConfig {
         RoseyPublicConfig {
@@ -46,9 +88,33 @@ impl Default for RoseyPublicConfig {
             images_source: None,
             default_language: "en".into(),
             redirect_page: None,
+            redirects: None,
+            skip_redirect_pages: false,
             default_language_at_root: false,
             wrap: None,
             wrap_class: None,
+            wrap_mode: "span".into(),
+            exports: "rosey/exports".into(),
+            export_format: "xliff".into(),
+            xliff_version: "1.2".into(),
+            pot: false,
+            context: false,
+            check_format: "json".into(),
+            fail_on: None,
+            sitemap: false,
+            incremental: false,
+            build_cache: "rosey/build-cache.json".into(),
+            fallbacks: BTreeMap::new(),
+            directions: BTreeMap::new(),
+            min_status: None,
+            max_lengths: BTreeMap::new(),
+            strict: false,
+            memory: "rosey/memory.json".into(),
+            prune_unused: false,
+            suggest_threshold: 75,
+            translate_locale: None,
+            provider: None,
+            providers: BTreeMap::new(),
             verbose: false,
         }
     }
@@ -65,7 +131,6 @@ pub fn load_config_files() -> Result<RoseyPublicConfig, Error> {
     figment
         .extract()
         .context("Couldn't resolve configuration from files or environment variables")
-        .map_err(Into::into)
 }
 
 impl Display for RoseyPublicConfig {
@@ -77,6 +142,13 @@ impl Display for RoseyPublicConfig {
         writeln!(f, "   - Base locale file:    {}", self.base.display())?;
         writeln!(f, "   - Base urls file:      {}", self.base_urls.display())?;
         writeln!(f, "   - Locales directory:   {}", self.locales.display())?;
+        writeln!(f, "   - Exports directory:   {}", self.exports.display())?;
+        writeln!(
+            f,
+            "   - Build cache file:    {}",
+            self.build_cache.display()
+        )?;
+        writeln!(f, "   - Memory file:         {}", self.memory.display())?;
 
         match &self.images_source {
             Some(s) => writeln!(f, "   - Images source:       {}", s.display())?,
@@ -97,12 +169,23 @@ impl Display for RoseyPublicConfig {
             writeln!(f, "   - Root URLs:           Generated redirect page")?;
 
             match &self.redirect_page {
+                _ if self.skip_redirect_pages => writeln!(
+                    f,
+                    "   - Redirect page:       * skipped, not writing redirect pages *"
+                )?,
                 Some(s) => writeln!(f, "   - Redirect page:       {}", s.display())?,
                 None => writeln!(
                     f,
                     "   - Redirect page:       * unset, using default redirect template *"
                 )?,
             }
+            match &self.redirects {
+                Some(formats) => writeln!(f, "   - Redirect rules:      {}", formats.join(", "))?,
+                None => writeln!(
+                    f,
+                    "   - Redirect rules:      * none passed, no server redirects written *"
+                )?,
+            }
         }
 
         writeln!(f, "  Options:")?;
@@ -119,6 +202,45 @@ impl Display for RoseyPublicConfig {
                 "   - Languages:           * none passed, outputting all available languages *"
             )?,
         }
+        if self.fallbacks.is_empty() {
+            writeln!(
+                f,
+                "   - Fallbacks:           * none set, falling back to the default language *"
+            )?;
+        }
+        for (locale, fallbacks) in self.fallbacks.iter() {
+            writeln!(
+                f,
+                "   - Fallbacks:           {locale} → {}",
+                fallbacks.join(" → ")
+            )?;
+        }
+        if self.max_lengths.is_empty() {
+            writeln!(
+                f,
+                "   - Max lengths:         * none set, only using data-rosey-maxlength *"
+            )?;
+        }
+        for (element, max_length) in self.max_lengths.iter() {
+            writeln!(f, "   - Max lengths:         <{element}> {max_length}")?;
+        }
+        writeln!(f, "   - Strict mode:         {}", self.strict)?;
+        match &self.min_status {
+            Some(status) => writeln!(f, "   - Minimum status:      {status}")?,
+            None => writeln!(
+                f,
+                "   - Minimum status:      * none set, using all translations *"
+            )?,
+        }
+        if self.directions.is_empty() {
+            writeln!(
+                f,
+                "   - Directions:          * none set, using the script of each locale *"
+            )?;
+        }
+        for (locale, direction) in self.directions.iter() {
+            writeln!(f, "   - Directions:          {locale} {direction}")?;
+        }
         match &self.wrap {
             Some(langs) => writeln!(f, "   - Wrap languages:      {}", langs.join(", "))?,
             None => writeln!(
@@ -133,6 +255,42 @@ impl Display for RoseyPublicConfig {
                 "   - Wrap classname:      * none set, wrapping with inline styles *"
             )?,
         }
+        writeln!(f, "   - Wrap mode:           {}", self.wrap_mode)?;
+        writeln!(f, "   - Write sitemap:       {}", self.sitemap)?;
+        writeln!(f, "   - Incremental build:   {}", self.incremental)?;
+        writeln!(f, "   - Generate POT file:   {}", self.pot)?;
+        writeln!(f, "   - Record context:      {}", self.context)?;
+        writeln!(f, "   - Check format:        {}", self.check_format)?;
+        match &self.fail_on {
+            Some(states) => writeln!(f, "   - Check fails on:      {}", states.join(", "))?,
+            None => writeln!(
+                f,
+                "   - Check fails on:      * none passed, check will always succeed *"
+            )?,
+        }
+        writeln!(f, "   - Suggest threshold:   {}%", self.suggest_threshold)?;
+        writeln!(f, "   - Prune unused keys:   {}", self.prune_unused)?;
+        if let Some(locale) = &self.translate_locale {
+            writeln!(f, "   - Translate locale:    {locale}")?;
+        }
+        match &self.provider {
+            Some(provider) => writeln!(f, "   - Provider:            {provider}")?,
+            None => writeln!(f, "   - Provider:            * none set *")?,
+        }
+        for (name, provider) in self.providers.iter() {
+            match provider {
+                RoseyProviderConfig::Http { url, .. } => {
+                    writeln!(f, "   - Providers:           {name} http {url}")?
+                }
+                RoseyProviderConfig::Command { command, args } => writeln!(
+                    f,
+                    "   - Providers:           {name} command {command} {}",
+                    args.join(" ")
+                )?,
+            }
+        }
+        writeln!(f, "   - Export format:       {}", self.export_format)?;
+        writeln!(f, "   - XLIFF version:       {}", self.xliff_version)?;
         write!(f, "")
     }
 }
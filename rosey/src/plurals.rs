@@ -0,0 +1,47 @@
+use fixed_decimal::Decimal;
+use icu_locale_core::Locale;
+use icu_plurals::{PluralCategory, PluralOperands, PluralRules};
+
+fn parse_locale(locale: &str) -> Option<Locale> {
+    locale.replace('_', "-").parse::<Locale>().ok()
+}
+
+fn plural_rules(locale: &str) -> Option<PluralRules> {
+    PluralRules::try_new_cardinal(parse_locale(locale)?.into()).ok()
+}
+
+fn ordinal_rules(locale: &str) -> Option<PluralRules> {
+    PluralRules::try_new_ordinal(parse_locale(locale)?.into()).ok()
+}
+
+fn category_name(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    }
+}
+
+pub fn plural_category(locale: &str, count: &str) -> &'static str {
+    match (plural_rules(locale), count.trim().parse::<Decimal>()) {
+        (Some(rules), Ok(count)) => category_name(rules.category_for(PluralOperands::from(&count))),
+        _ => "other",
+    }
+}
+
+pub fn ordinal_category(locale: &str, count: &str) -> &'static str {
+    match (ordinal_rules(locale), count.trim().parse::<Decimal>()) {
+        (Some(rules), Ok(count)) => category_name(rules.category_for(PluralOperands::from(&count))),
+        _ => "other",
+    }
+}
+
+pub fn plural_categories(locale: &str) -> Vec<&'static str> {
+    match plural_rules(locale) {
+        Some(rules) => rules.categories().map(category_name).collect(),
+        None => vec!["other"],
+    }
+}
@@ -1,57 +1,99 @@
+mod cache;
 mod html;
 mod json;
+mod markdown;
 mod redirect_page;
+mod redirects;
 mod serve;
+mod sitemap;
+mod yaml;
 
 use std::{
-    collections::BTreeMap,
-    fs::{copy, create_dir_all, read_to_string, remove_dir_all, File},
-    io::{BufWriter, Write},
+    collections::{BTreeMap, BTreeSet},
+    fs::{copy, create_dir_all, read, read_to_string, remove_dir_all},
     path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
 use notify::{
     event::{CreateKind, ModifyKind},
     Event, EventKind, RecursiveMode, Watcher,
 };
+use path_slash::PathExt as _;
 use rayon::prelude::*;
 use regex::Regex;
 
-use crate::{RoseyOptions, RoseyTranslation};
+use crate::{fallback_chain, RoseyError, RoseyOptions, RoseyTranslation, RoseyWarnings};
+
+use super::{data_file_extension, parse_locale_file, write_output};
+
+use self::{
+    cache::{hash_content, RoseyBuildCache, RoseyBuildManifestFile},
+    html::filepath_to_output_url,
+    json::{data_file_schema, is_schema_file},
+    markdown::is_markdown_file,
+};
 
 pub struct RoseyBuilder {
     options: RoseyOptions,
+    cache: RoseyBuildCache,
     pub translations: BTreeMap<String, RoseyTranslation>,
     pub url_translations: BTreeMap<String, RoseyTranslation>,
+    overlong: AtomicUsize,
+    pub(crate) warnings: RoseyWarnings,
 }
 
 impl From<RoseyOptions> for RoseyBuilder {
     fn from(options: RoseyOptions) -> Self {
         RoseyBuilder {
             options,
+            cache: RoseyBuildCache::default(),
             translations: BTreeMap::default(),
             url_translations: BTreeMap::default(),
+            overlong: AtomicUsize::new(0),
+            warnings: RoseyWarnings::default(),
         }
     }
 }
 
 impl RoseyBuilder {
-    pub async fn run(mut self) {
-        self.clean_output_dir();
-        self.read_translations();
-        self.process_assets();
-        self.process_files();
+    pub async fn run(mut self) -> Result<(), RoseyError> {
+        let result = self.build();
+        self.warnings.print();
+        result?;
 
         if self.options.serve {
-            self.serve().await;
+            self.serve().await?;
+        }
+        Ok(())
+    }
+
+    pub fn build(&mut self) -> Result<(), RoseyError> {
+        if !self.options.config.incremental {
+            self.clean_output_dir()?;
+        }
+        self.read_translations()?;
+        if self.options.config.incremental {
+            self.load_build_cache()?;
         }
+        self.process_assets()?;
+        self.process_files()?;
+
+        let overlong = self.overlong.load(Ordering::Relaxed);
+        if self.options.config.strict && overlong > 0 {
+            return Err(RoseyError::OverlongTranslations(overlong));
+        }
+
+        self.write_sitemap()?;
+        self.write_redirects()?;
+        self.write_build_cache()
     }
 
-    async fn serve(self) {
+    async fn serve(self) -> Result<(), RoseyError> {
         let config = &self.options.config;
         let source_dir = config.source.clone();
         let dest_dir = config.dest.clone();
-        let re = Regex::new(&config.exclusions).expect("Invalid regex");
+        let re = self.exclusions()?;
         let mut watcher = notify::recommended_watcher(move |res| match res {
             Ok(Event {
                 kind:
@@ -62,44 +104,60 @@ impl RoseyBuilder {
             }) => {
                 println!("Rebuilding...");
                 paths.iter().for_each(|path| {
-                    if !re.is_match(&path.to_string_lossy()) {
-                        return self.process_asset(path);
+                    let result = if !re.is_match(&path.to_string_lossy()) {
+                        self.process_asset(path).and_then(|_| {
+                            if is_markdown_file(path) || data_file_extension(path).is_some() {
+                                self.process_file(path)?;
+                            }
+                            Ok(())
+                        })
+                    } else if self.find_locale_overwrite(path).is_some() {
+                        self.process_file(path)
+                    } else {
+                        self.process_file(path)
+                            .and_then(|_| self.process_file_overrides(path))
+                    };
+                    if let Err(e) = result {
+                        eprintln!("{e}");
                     }
-
-                    if self.find_locale_overwrite(path).is_some() {
-                        return self.process_file(path);
-                    }
-
-                    self.process_file(path);
-                    self.process_file_overrides(path);
                 });
+                self.warnings.print();
                 println!("Done!");
             }
             Err(e) => println!("watch error: {:?}", e),
             _ => (),
         })
-        .unwrap();
+        .map_err(|e| RoseyError::io(&source_dir, std::io::Error::other(e)))?;
 
         watcher
             .watch(&source_dir, RecursiveMode::Recursive)
-            .unwrap();
+            .map_err(|e| RoseyError::io(&source_dir, std::io::Error::other(e)))?;
 
         serve::serve_dir(dest_dir.clone()).await;
+        Ok(())
+    }
+
+    fn exclusions(&self) -> Result<Regex, RoseyError> {
+        let exclusions = &self.options.config.exclusions;
+        Regex::new(exclusions).map_err(|e| {
+            RoseyError::InvalidConfig(format!("Invalid exclusions regex '{exclusions}': {e}"))
+        })
     }
 
-    pub fn clean_output_dir(&self) {
+    pub fn clean_output_dir(&self) -> Result<(), RoseyError> {
         let dest_folder = &self.options.config.dest;
         if dest_folder.exists() {
-            remove_dir_all(dest_folder).expect("Failed to clean destination folder");
+            remove_dir_all(dest_folder).map_err(|e| RoseyError::io(dest_folder, e))?;
         }
+        Ok(())
     }
 
-    pub fn process_assets(&self) {
+    pub fn process_assets(&self) -> Result<(), RoseyError> {
         let config = &self.options.config;
-        let re = Regex::new(&config.exclusions).expect("Invalid regex");
+        let re = self.exclusions()?;
         let walker = globwalk::GlobWalkerBuilder::from_patterns(&config.source, &["**/*"])
             .build()
-            .unwrap()
+            .map_err(|e| RoseyError::io(&config.source, std::io::Error::other(e)))?
             .filter_map(Result::ok)
             .filter(|file| {
                 file.file_type().is_file() && !re.is_match(&file.path().to_string_lossy())
@@ -108,42 +166,113 @@ impl RoseyBuilder {
         walker
             .collect::<Vec<_>>()
             .par_iter()
-            .for_each(|file| self.process_asset(file.path()));
+            .try_for_each(|file| self.process_asset(file.path()))
     }
 
-    pub fn process_asset(&self, path: &Path) {
+    pub fn process_asset(&self, path: &Path) -> Result<(), RoseyError> {
         let config = &self.options.config;
         let relative_path = path.strip_prefix(&config.source).unwrap();
         let dest_file = &config.dest.join(relative_path);
 
         if let Some(parent) = dest_file.parent() {
-            create_dir_all(parent).unwrap();
+            create_dir_all(parent).map_err(|e| RoseyError::io(parent, e))?;
+        }
+
+        if path == dest_file
+            || is_schema_file(path)
+            || data_file_schema(path).is_some()
+            || is_markdown_file(path)
+        {
+            return Ok(());
         }
 
-        if path != dest_file {
-            copy(path, dest_file).unwrap();
+        if !self.options.config.incremental {
+            copy(path, dest_file).map_err(|e| RoseyError::io(path, e))?;
+            return Ok(());
         }
+
+        let relative_key = relative_path.to_slash_lossy().to_string();
+        let hash = hash_content(&read(path).map_err(|e| RoseyError::io(path, e))?);
+        if self.reuse_cached_file(&relative_key, &hash, |_| BTreeMap::default()) {
+            return Ok(());
+        }
+
+        copy(path, dest_file).map_err(|e| RoseyError::io(path, e))?;
+        self.record_file(&relative_key, || RoseyBuildManifestFile {
+            hash,
+            outputs: self.output_paths(&[dest_file.to_owned()]),
+            ..Default::default()
+        });
+        Ok(())
     }
 
-    pub fn process_files(&self) {
+    pub fn process_files(&self) -> Result<(), RoseyError> {
         let config = &self.options.config;
         let source_folder = &config.source;
-        let walker: (Vec<_>, Vec<_>) =
-            globwalk::GlobWalkerBuilder::from_patterns(source_folder, &["**/*.{html,json}"])
-                .build()
-                .unwrap()
-                .filter_map(Result::ok)
-                .partition(|file| self.find_locale_overwrite(file.path()).is_none());
+        let walker: (Vec<_>, Vec<_>) = globwalk::GlobWalkerBuilder::from_patterns(
+            source_folder,
+            &["**/*.{html,json,yml,yaml,toml,md,txt}"],
+        )
+        .build()
+        .map_err(|e| RoseyError::io(source_folder, std::io::Error::other(e)))?
+        .filter_map(Result::ok)
+        .partition(|file| self.find_locale_overwrite(file.path()).is_none());
 
         walker
             .0
             .par_iter()
-            .for_each(|file| self.process_file(file.path()));
+            .try_for_each(|file| self.process_file(file.path()))?;
 
         walker
             .1
             .par_iter()
-            .for_each(|file| self.process_file(file.path()));
+            .try_for_each(|file| self.process_file(file.path()))
+    }
+
+    pub fn locale_lookup(&self) -> BTreeMap<String, &str> {
+        let config = &self.options.config;
+        let mut lookup: BTreeMap<String, &str> = BTreeMap::default();
+        for key in self
+            .translations
+            .keys()
+            .chain(std::iter::once(&config.default_language))
+        {
+            let mut split = key.split('-');
+            let language = split.next().unwrap();
+
+            lookup.insert(key.to_string(), key);
+            lookup.insert(language.to_string(), key);
+            if let Some(country) = split.next() {
+                lookup.insert(format!("{language}-{country}"), key);
+                lookup.insert(format!("{language}_{country}"), key);
+            }
+        }
+        lookup
+    }
+
+    pub fn source_pages(&self) -> Vec<String> {
+        let source_folder = &self.options.config.source;
+        let mut pages =
+            globwalk::GlobWalkerBuilder::from_patterns(source_folder, &["**/*.{html,htm}"])
+                .build()
+                .unwrap()
+                .filter_map(Result::ok)
+                .filter(|file| self.find_locale_overwrite(file.path()).is_none())
+                .map(|file| {
+                    let relative_path = file.path().strip_prefix(source_folder).unwrap();
+                    relative_path.to_slash_lossy().to_string()
+                })
+                .collect::<Vec<_>>();
+        pages.sort();
+        pages
+    }
+
+    pub fn translated_url(&self, path: &str, locale: &str) -> String {
+        self.url_translations
+            .get(locale)
+            .and_then(|t| t.get(path))
+            .map(|p| filepath_to_output_url(p))
+            .unwrap_or_else(|| filepath_to_output_url(path))
     }
 
     fn find_locale_overwrite(&self, path: &Path) -> Option<&String> {
@@ -158,68 +287,103 @@ impl RoseyBuilder {
         })
     }
 
-    pub fn read_translations(&mut self) {
+    pub fn read_translations(&mut self) -> Result<(), RoseyError> {
         let config = &self.options.config;
-        let walker = globwalk::GlobWalkerBuilder::from_patterns(&config.locales, &["**/*.json"])
-            .build()
-            .unwrap()
-            .filter_map(Result::ok);
+        let walker =
+            globwalk::GlobWalkerBuilder::from_patterns(&config.locales, &["**/*.{json,po}"])
+                .build()
+                .map_err(|e| RoseyError::io(&config.locales, std::io::Error::other(e)))?
+                .filter_map(Result::ok);
 
-        walker.for_each(|file| {
+        for file in walker {
             let locale = file
                 .path()
                 .file_stem()
                 .unwrap()
                 .to_string_lossy()
                 .to_string();
-            let value = read_to_string(file.path()).expect("Failed to read locale file");
-            let value = serde_json::from_str(&value);
-            if let Ok(value) = value {
+            let value = read_to_string(file.path()).map_err(|e| RoseyError::io(file.path(), e))?;
+            if let Ok(mut value) = parse_locale_file(file.path(), &value, &locale) {
                 if locale.ends_with(".urls") {
                     self.url_translations
                         .insert(locale.trim_end_matches(".urls").to_string(), value);
                 } else {
+                    if let Some(min_status) = &config.min_status {
+                        value.remove_unreviewed(min_status);
+                    }
                     self.translations.insert(locale, value);
                 }
             }
-        });
+        }
+
+        let fallbacks = &self.options.config.fallbacks;
+        if !fallbacks.is_empty() {
+            let locales = self.translations.keys().cloned().collect();
+            self.translations = with_fallbacks(&self.translations, &locales, fallbacks);
+
+            let locales = self
+                .url_translations
+                .keys()
+                .chain(self.translations.keys())
+                .cloned()
+                .collect();
+            self.url_translations = with_fallbacks(&self.url_translations, &locales, fallbacks);
+        }
+        Ok(())
     }
 
-    pub fn process_file(&self, file: &Path) {
+    pub fn process_file(&self, file: &Path) -> Result<(), RoseyError> {
         match file.extension().map(|ext| ext.to_str().unwrap()) {
             Some("htm" | "html") => self.process_html_file(file),
-            Some("json") => self.process_json_file(file),
+            Some("json" | "yml" | "yaml" | "toml") => self.process_json_file(file),
+            Some("md" | "txt") => self.process_markdown_file(file),
             _ => unreachable!("Tried to process unknown file type."),
         }
     }
 
-    pub fn process_file_overrides(&self, path: &Path) {
+    pub fn process_file_overrides(&self, path: &Path) -> Result<(), RoseyError> {
         let config = &self.options.config;
         let source_folder = &config.source;
         let relative_path = path.strip_prefix(source_folder).unwrap();
         self.translations
             .par_iter()
             .filter(|(locale, _)| source_folder.join(locale).join(relative_path).exists())
-            .for_each(|(locale, _)| {
+            .try_for_each(|(locale, _)| {
                 self.process_file(&source_folder.join(locale).join(relative_path))
-            });
+            })
     }
 
-    pub fn output_file(&self, locale: &str, relative_path: &Path, content: String) {
-        let config = &self.options.config;
-        let dest_folder = &config.dest.join(locale);
-        let dest_path = dest_folder.join(relative_path);
-        if let Some(parent) = dest_path.parent() {
-            create_dir_all(parent).unwrap();
-        }
+    pub fn output_file(
+        &self,
+        locale: &str,
+        relative_path: &Path,
+        content: String,
+    ) -> Result<(), RoseyError> {
+        let dest_path = self.options.config.dest.join(locale).join(relative_path);
+        write_output(&dest_path, &content)
+    }
+}
 
-        if let Ok(file) = File::create(&dest_path) {
-            let mut writer = BufWriter::new(file);
-            if writer.write(content.as_bytes()).is_err() {
-                eprintln!("Failed to write: {dest_path:?}")
+// Fills in keys missing from each locale using the locales it falls back to.
+fn with_fallbacks(
+    translations: &BTreeMap<String, RoseyTranslation>,
+    locales: &BTreeSet<String>,
+    fallbacks: &BTreeMap<String, Vec<String>>,
+) -> BTreeMap<String, RoseyTranslation> {
+    locales
+        .iter()
+        .filter_map(|locale| {
+            let mut merged: Option<RoseyTranslation> = None;
+            for fallback in fallback_chain(fallbacks, locale) {
+                let Some(translation) = translations.get(&fallback) else {
+                    continue;
+                };
+                match &mut merged {
+                    Some(merged) => merged.merge_fallback(translation),
+                    None => merged = Some(translation.clone()),
+                }
             }
-        } else {
-            eprintln!("Failed to open: {dest_path:?}")
-        }
-    }
+            merged.map(|merged| (locale.clone(), merged))
+        })
+        .collect()
 }
@@ -0,0 +1,289 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{read, read_to_string, remove_dir, remove_file},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use base64::prelude::*;
+use path_slash::PathExt as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::RoseyBuilder;
+use crate::{runners::write_output, RoseyError, RoseyTranslation};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct RoseyBuildManifest {
+    pub environment: String,
+    pub files: BTreeMap<String, RoseyBuildManifestFile>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct RoseyBuildManifestFile {
+    pub hash: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, String>,
+    pub outputs: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct RoseyBuildCache {
+    previous: RoseyBuildManifest,
+    files: Mutex<BTreeMap<String, RoseyBuildManifestFile>>,
+    rebuilt: Mutex<BTreeSet<String>>,
+    locale_hashes: BTreeMap<String, String>,
+    environment: String,
+}
+
+pub fn hash_content(content: &[u8]) -> String {
+    BASE64_STANDARD_NO_PAD.encode(Sha256::digest(content))
+}
+
+// Only the text of an entry, with any fallbacks already merged in, changes the
+// output. Its other fields, such as page counts or notes, don't rebuild the page.
+fn hash_entry(hasher: &mut Sha256, translation: &RoseyTranslation, key: &str) {
+    hasher.update(key);
+    hasher.update([0]);
+    let (value, plurals) = match translation {
+        RoseyTranslation::V1(keys) => (keys.get(key), None),
+        RoseyTranslation::V2(keys) => keys.get(key).map_or((None, None), |entry| {
+            (entry.value.as_ref(), entry.plurals.as_ref())
+        }),
+    };
+    if let Some(value) = value {
+        hasher.update(value);
+    }
+    hasher.update([0]);
+    for (form, value) in plurals.into_iter().flatten() {
+        hasher.update(form);
+        hasher.update([0]);
+        hasher.update(value);
+        hasher.update([0]);
+    }
+    hasher.update([1]);
+}
+
+impl RoseyBuilder {
+    pub fn load_build_cache(&mut self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+
+        self.cache.locale_hashes = self
+            .translations
+            .iter()
+            .map(|(locale, translation)| {
+                let keys = match translation {
+                    RoseyTranslation::V1(keys) => keys.keys().collect::<Vec<_>>(),
+                    RoseyTranslation::V2(keys) => keys.keys().collect(),
+                };
+                let mut hasher = Sha256::new();
+                for key in keys {
+                    hash_entry(&mut hasher, translation, key);
+                }
+                (
+                    locale.clone(),
+                    BASE64_STANDARD_NO_PAD.encode(hasher.finalize()),
+                )
+            })
+            .collect();
+        self.cache.environment = self.environment_hash();
+
+        let previous = read_to_string(&config.build_cache)
+            .ok()
+            .and_then(|value| serde_json::from_str::<RoseyBuildManifest>(&value).ok());
+
+        match previous {
+            Some(previous) if previous.environment == self.cache.environment => {
+                self.cache.previous = previous;
+                Ok(())
+            }
+            _ => self.clean_output_dir(),
+        }
+    }
+
+    fn environment_hash(&self) -> String {
+        let config = &self.options.config;
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        hasher.update(serde_json::to_string(config).unwrap());
+        hasher.update(serde_json::to_string(&self.url_translations).unwrap());
+        for locale in self.translations.keys() {
+            hasher.update(locale);
+            hasher.update([0]);
+        }
+        if let Some(redirect_page) = &config.redirect_page {
+            hasher.update(read(redirect_page).unwrap_or_default());
+        }
+
+        // Locale overrides and translated assets change the output of other pages,
+        // so adding or removing one of them invalidates the whole build.
+        let mut roots = vec![&config.source];
+        if let Some(images_source) = &config.images_source {
+            roots.push(images_source);
+        }
+        for root in roots {
+            let mut localized_paths = globwalk::GlobWalkerBuilder::from_patterns(root, &["**/*"])
+                .build()
+                .unwrap()
+                .filter_map(Result::ok)
+                .filter(|file| file.file_type().is_file())
+                .filter_map(|file| {
+                    let relative_path = file.path().strip_prefix(root).unwrap();
+                    self.is_localized_path(relative_path)
+                        .then(|| relative_path.to_slash_lossy().to_string())
+                })
+                .collect::<Vec<_>>();
+            localized_paths.sort();
+            for path in localized_paths {
+                hasher.update(path);
+                hasher.update([0]);
+            }
+        }
+
+        BASE64_STANDARD_NO_PAD.encode(hasher.finalize())
+    }
+
+    fn is_localized_path(&self, relative_path: &Path) -> bool {
+        let config = &self.options.config;
+        let is_locale =
+            |value: &str| value == config.default_language || self.translations.contains_key(value);
+
+        let in_locale_folder = relative_path
+            .components()
+            .next()
+            .is_some_and(|first| is_locale(&first.as_os_str().to_string_lossy()));
+        let is_translated_asset = relative_path
+            .file_stem()
+            .and_then(|stem| Path::new(stem).extension())
+            .is_some_and(|locale| is_locale(&locale.to_string_lossy()));
+
+        in_locale_folder || is_translated_asset
+    }
+
+    pub fn translation_hashes(
+        &self,
+        keys: &[String],
+        locales: &[&String],
+    ) -> BTreeMap<String, String> {
+        locales
+            .iter()
+            .filter_map(|locale| {
+                let translation = self.translations.get(*locale)?;
+                let mut hasher = Sha256::new();
+                for key in keys {
+                    hash_entry(&mut hasher, translation, key);
+                }
+                Some((
+                    locale.to_string(),
+                    BASE64_STANDARD_NO_PAD.encode(hasher.finalize()),
+                ))
+            })
+            .collect()
+    }
+
+    pub fn locale_hashes(&self) -> BTreeMap<String, String> {
+        self.cache.locale_hashes.clone()
+    }
+
+    pub fn reuse_cached_file(
+        &self,
+        relative_path: &str,
+        hash: &str,
+        translation_hashes: impl FnOnce(&[String]) -> BTreeMap<String, String>,
+    ) -> bool {
+        if !self.options.config.incremental {
+            return false;
+        }
+
+        let Some(previous) = self.cache.previous.files.get(relative_path) else {
+            return false;
+        };
+        let dest_folder = &self.options.config.dest;
+        if previous.hash != hash
+            || previous.translations != translation_hashes(&previous.keys)
+            || !previous
+                .outputs
+                .iter()
+                .all(|output| dest_folder.join(output).exists())
+        {
+            return false;
+        }
+
+        self.cache
+            .files
+            .lock()
+            .unwrap()
+            .insert(relative_path.to_string(), previous.clone());
+        true
+    }
+
+    pub fn was_rebuilt(&self, relative_path: &str) -> bool {
+        self.cache.rebuilt.lock().unwrap().contains(relative_path)
+    }
+
+    pub fn record_file(&self, relative_path: &str, entry: impl FnOnce() -> RoseyBuildManifestFile) {
+        if !self.options.config.incremental {
+            return;
+        }
+
+        self.cache
+            .rebuilt
+            .lock()
+            .unwrap()
+            .insert(relative_path.to_string());
+        self.cache
+            .files
+            .lock()
+            .unwrap()
+            .insert(relative_path.to_string(), entry());
+    }
+
+    pub fn output_paths(&self, outputs: &[PathBuf]) -> Vec<String> {
+        let dest_folder = &self.options.config.dest;
+        outputs
+            .iter()
+            .filter_map(|output| output.strip_prefix(dest_folder).ok())
+            .map(|output| output.to_slash_lossy().to_string())
+            .collect()
+    }
+
+    pub fn write_build_cache(&mut self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        if !config.incremental {
+            return Ok(());
+        }
+
+        let previous = std::mem::take(&mut self.cache.previous);
+        let files = std::mem::take(&mut *self.cache.files.lock().unwrap());
+
+        let outputs = files
+            .values()
+            .flat_map(|file| file.outputs.iter())
+            .collect::<BTreeSet<_>>();
+        for output in previous
+            .files
+            .values()
+            .flat_map(|file| file.outputs.iter())
+            .filter(|output| !outputs.contains(output))
+        {
+            let output_path = config.dest.join(output);
+            if remove_file(&output_path).is_err() {
+                continue;
+            }
+            for parent in output_path.ancestors().skip(1) {
+                if parent == config.dest || remove_dir(parent).is_err() {
+                    break;
+                }
+            }
+        }
+
+        let manifest = RoseyBuildManifest {
+            environment: self.cache.environment.clone(),
+            files,
+        };
+        let output = serde_json::to_string(&manifest).unwrap();
+        write_output(&config.build_cache, &output)
+    }
+}
@@ -2,20 +2,23 @@ mod utils;
 
 use self::utils::{RoseySerializer, TranslationRewriter};
 
-use super::{redirect_page, RoseyBuilder};
+use super::{
+    cache::{hash_content, RoseyBuildManifestFile},
+    redirect_page, write_output, RoseyBuilder,
+};
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::Write as FmtWrite,
     fs::File,
     fs::{create_dir_all, read_to_string},
     io::{BufWriter, Write},
     path::{Path, PathBuf, MAIN_SEPARATOR},
     str::FromStr,
+    sync::atomic::Ordering,
 };
 
 use base64::prelude::*;
-use charabia::Segment;
 use html5ever::{
     buffer_queue::BufferQueue,
     local_name, namespace_url, ns,
@@ -26,23 +29,50 @@ use html5ever::{
 };
 use kuchiki::{traits::TendrilSink, Attribute, ExpandedName, NodeRef};
 use path_slash::PathExt as _;
+use serde_json::Value;
 use sha2::{Digest, Sha256};
 use url::Url;
-use utils::filepath_to_output_url;
-
-use crate::RoseyTranslation;
+pub use utils::filepath_to_output_url;
+
+use crate::{
+    attribute_max_length,
+    direction::{is_opposite_direction, is_rtl_locale, isolate_opposite_runs},
+    element_max_length, element_message_args, fallback_chain,
+    locale_format::format_value,
+    messageformat::format_message,
+    text_length,
+    wrap::{wrap_words, wraps_locale},
+    RoseyError, RoseyTranslation,
+};
 
 const UNSUPPORTED_WRAP_ELEMENTS: [&str; 6] = ["title", "script", "style", "text", "tspan", "textPath"];
 
 impl RoseyBuilder {
-    pub fn process_html_file(&self, file: &Path) {
+    pub fn process_html_file(&self, file: &Path) -> Result<(), RoseyError> {
         let config = &self.options.config;
         let source_folder = &config.source;
         let relative_path = file.strip_prefix(source_folder).unwrap();
         let dest_folder = &config.dest;
         let images_source = config.images_source.as_ref().unwrap_or(&config.source);
 
-        let content = read_to_string(file).unwrap();
+        let content = read_to_string(file).map_err(|e| RoseyError::io(file, e))?;
+        let hash = hash_content(content.as_bytes());
+        let relative_key = relative_path.to_slash_lossy().to_string();
+        let locale_overwrite = self.find_locale_overwrite(file);
+        let locales = match locale_overwrite {
+            Some(key) => vec![key],
+            None => self.translations.keys().collect::<Vec<_>>(),
+        };
+        let base_rebuilt = locale_overwrite.is_some_and(|key| {
+            self.was_rebuilt(&relative_path.strip_prefix(key).unwrap().to_slash_lossy())
+        });
+        if !base_rebuilt
+            && self.reuse_cached_file(&relative_key, &hash, |keys| {
+                self.translation_hashes(keys, &locales)
+            })
+        {
+            return Ok(());
+        }
 
         let mut page = RoseyPage::new(
             content,
@@ -53,13 +83,19 @@ impl RoseyBuilder {
             &config.default_language,
             config.default_language_at_root,
             &self.translations,
+            &config.fallbacks,
             &config.wrap,
             &config.wrap_class,
+            &config.wrap_mode,
+            &config.max_lengths,
+            &config.directions,
         );
         page.prepare();
+        let keys = page.translation_keys();
+        let mut outputs = vec![];
 
         //If the file is already in a locale folder, then output it only for that locale
-        if let Some(key) = self.find_locale_overwrite(file) {
+        if let Some(key) = locale_overwrite {
             let url_translations = self.url_translations.get(key);
             let original_relative_path = relative_path.strip_prefix(key).unwrap();
 
@@ -71,8 +107,17 @@ impl RoseyBuilder {
             page.rewrite_anchors(url_translations);
 
             let output_path = dest_folder.join(relative_path);
-            page.output_file(&output_path);
-            return;
+            page.output_file(&output_path)?;
+            outputs.push(output_path);
+            self.report_overlong(&relative_key, &page);
+
+            self.record_file(&relative_key, || RoseyBuildManifestFile {
+                hash,
+                translations: self.translation_hashes(&keys, &locales),
+                keys,
+                outputs: self.output_paths(&outputs),
+            });
+            return Ok(());
         }
 
         page.rewrite_meta_tags(relative_path, &self.url_translations);
@@ -87,21 +132,26 @@ impl RoseyBuilder {
 
         if config.default_language_at_root {
             let output_path = dest_folder.join(translated_default_url);
-            page.output_file(&output_path);
+            page.output_file(&output_path)?;
+            outputs.push(output_path);
         } else {
             let output_path = dest_folder
                 .join(&config.default_language)
                 .join(translated_default_url);
-            page.output_file(&output_path);
-
-            self.output_redirect_file(
-                &config.default_language,
-                relative_path,
-                &self.url_translations,
-            );
+            page.output_file(&output_path)?;
+            outputs.push(output_path);
+
+            if !config.skip_redirect_pages {
+                self.output_redirect_file(
+                    &config.default_language,
+                    relative_path,
+                    &self.url_translations,
+                )?;
+                outputs.push(dest_folder.join(relative_path));
+            }
         }
 
-        self.translations.keys().for_each(|key| {
+        for key in self.translations.keys() {
             let url_translations = self.url_translations.get(key);
 
             let translated_url = url_translations
@@ -117,8 +167,26 @@ impl RoseyBuilder {
             page.rewrite_anchors(url_translations);
 
             let output_path = dest_folder.join(key).join(translated_url);
-            page.output_file(&output_path);
+            page.output_file(&output_path)?;
+            outputs.push(output_path);
+        }
+        self.report_overlong(&relative_key, &page);
+
+        self.record_file(&relative_key, || RoseyBuildManifestFile {
+            hash,
+            translations: self.translation_hashes(&keys, &locales),
+            keys,
+            outputs: self.output_paths(&outputs),
         });
+        Ok(())
+    }
+
+    fn report_overlong(&self, file: &str, page: &RoseyPage) {
+        for warning in page.warnings.iter().chain(page.overlong.iter()) {
+            self.warnings.warn(file, warning);
+        }
+        self.overlong
+            .fetch_add(page.overlong.len(), Ordering::Relaxed);
     }
 
     pub fn output_redirect_file(
@@ -126,18 +194,14 @@ impl RoseyBuilder {
         locale: &str,
         relative_path: &Path,
         url_translations: &BTreeMap<String, RoseyTranslation>,
-    ) {
+    ) -> Result<(), RoseyError> {
         let config = &self.options.config;
         let dest_folder = &config.dest;
         let dest_file = dest_folder.join(relative_path);
         let path = filepath_to_output_url(&relative_path.to_slash_lossy());
 
-        if let Some(parent) = dest_file.parent() {
-            create_dir_all(parent).unwrap();
-        }
-
         let output = if let Some(redirect_page) = &config.redirect_page {
-            read_to_string(redirect_page).expect("Failed to load custom redirect page.")
+            read_to_string(redirect_page).map_err(|e| RoseyError::io(redirect_page, e))?
         } else {
             redirect_page::DEFAULT.to_string()
         };
@@ -161,23 +225,13 @@ impl RoseyBuilder {
             )
             .expect("Failed to output redirect - alternate link");
         }
+        write!(
+            alternates,
+            r#"<link rel="alternate" href="/{path}" hreflang="x-default">"#
+        )
+        .expect("Failed to output redirect - x-default link");
 
-        let mut lookup: BTreeMap<String, &str> = BTreeMap::default();
-        for key in self
-            .translations
-            .keys()
-            .chain(std::iter::once(&config.default_language))
-        {
-            let mut split = key.split('-');
-            let language = split.next().unwrap();
-
-            lookup.insert(key.to_string(), key);
-            lookup.insert(language.to_string(), key);
-            if let Some(country) = split.next() {
-                lookup.insert(format!("{language}-{country}"), key);
-                lookup.insert(format!("{language}_{country}"), key);
-            }
-        }
+        let lookup = self.locale_lookup();
 
         let translated_default_url = if let Some(translated_url) = url_translations
             .get(locale)
@@ -197,36 +251,55 @@ impl RoseyBuilder {
             output = output.replace("LOCALE_LOOKUP", &lookup)
         }
 
-        if let Ok(file) = File::create(&dest_file) {
-            let mut writer = BufWriter::new(file);
-            if writer.write(output.as_bytes()).is_err() {
-                eprintln!("Failed to write: {dest_file:?}")
-            }
-        } else {
-            eprintln!("Failed to open: {dest_file:?}")
-        }
+        write_output(&dest_file, &output)
     }
 }
 
 enum RoseyEdit {
-    Content(String, String, NodeRef),
+    Content(String, String, RoseyOriginalAttributes, NodeRef),
     Attribute(String, String, Option<String>, NodeRef),
+    Format(String, String, String, NodeRef),
+}
+
+// Attributes of a translated element that Rosey changes for each locale
+struct RoseyOriginalAttributes {
+    dir: Option<String>,
+    style: Option<String>,
+    class: Option<String>,
 }
 
+type RoseyImageTag = (
+    Option<String>,
+    Option<String>,
+    NodeRef,
+    Option<BTreeMap<String, String>>,
+);
+
 struct RoseyPage<'a> {
     dom: NodeRef,
     edits: Vec<RoseyEdit>,
     html_tag: Option<NodeRef>,
+    html_dir: Option<String>,
+    source_rtl: bool,
     meta_tag: Option<NodeRef>,
     link_tags: Vec<NodeRef>,
-    image_tags: Vec<(Option<String>, Option<String>, NodeRef, Option<BTreeMap<String, String>>)>,
+    x_default_tag: Option<NodeRef>,
+    canonical_tags: Vec<(String, NodeRef)>,
+    image_tags: Vec<RoseyImageTag>,
     anchor_tags: Vec<(String, NodeRef)>,
     assets: Vec<(String, String, NodeRef)>,
     locale_key: Option<&'a str>,
+    locale_chain: Vec<String>,
+    fallbacks: &'a BTreeMap<String, Vec<String>>,
     should_wrap: bool,
     default_language_at_root: bool,
     wrap: &'a Option<Vec<String>>,
     wrap_class: &'a Option<String>,
+    wrap_mode: &'a str,
+    max_lengths: &'a BTreeMap<String, u32>,
+    directions: &'a BTreeMap<String, String>,
+    overlong: Vec<String>,
+    warnings: BTreeSet<String>,
     pub tag: String,
     pub base_url: String,
     pub separator: String,
@@ -236,6 +309,7 @@ struct RoseyPage<'a> {
 }
 
 impl<'a> RoseyPage<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         content: String,
         separator: &str,
@@ -245,8 +319,12 @@ impl<'a> RoseyPage<'a> {
         default_language: &str,
         default_language_at_root: bool,
         translations: &'a BTreeMap<String, RoseyTranslation>,
+        fallbacks: &'a BTreeMap<String, Vec<String>>,
         wrap: &'a Option<Vec<String>>,
         wrap_class: &'a Option<String>,
+        wrap_mode: &'a str,
+        max_lengths: &'a BTreeMap<String, u32>,
+        directions: &'a BTreeMap<String, String>,
     ) -> Self {
         let dom = kuchiki::parse_html().one(content);
         crate::inline_templates(&dom);
@@ -256,6 +334,8 @@ impl<'a> RoseyPage<'a> {
             dom,
             edits: Vec::new(),
             link_tags: Vec::new(),
+            x_default_tag: None,
+            canonical_tags: Vec::new(),
             image_tags: Vec::new(),
             anchor_tags: Vec::new(),
             assets: Vec::new(),
@@ -263,24 +343,46 @@ impl<'a> RoseyPage<'a> {
             tag: tag.to_string(),
             base_url: base_url.to_string(),
             html_tag: None,
+            html_dir: None,
+            source_rtl: false,
             meta_tag: None,
             images_source,
             default_language: default_language.to_string(),
             translations,
             locale_key: None,
+            locale_chain: vec![default_language.to_string()],
+            fallbacks,
             should_wrap: false,
             default_language_at_root,
             wrap,
             wrap_class,
+            wrap_mode,
+            max_lengths,
+            directions,
+            overlong: Vec::new(),
+            warnings: BTreeSet::new(),
         }
     }
 
+    pub fn translation_keys(&self) -> Vec<String> {
+        self.edits
+            .iter()
+            .filter_map(|edit| match edit {
+                RoseyEdit::Content(key, ..) | RoseyEdit::Attribute(key, ..) => Some(key.clone()),
+                RoseyEdit::Format(..) => None,
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
     pub fn set_locale_key(&mut self, locale_key: &'a str) {
         self.locale_key = Some(locale_key);
+        self.locale_chain = fallback_chain(self.fallbacks, locale_key);
         self.should_wrap = self
             .wrap
             .as_ref()
-            .map_or(false, |langs| langs.contains(&locale_key.to_string()));
+            .is_some_and(|wrap| wraps_locale(locale_key, wrap));
     }
 
     fn get_locale_key(&self) -> &str {
@@ -346,14 +448,7 @@ impl<'a> RoseyPage<'a> {
 
             if let Some(urlmap) = url_translations {
                 let rel_url = parsed.path().trim_start_matches('/');
-
-                let candidate_url = if rel_url.ends_with('/') {
-                    format!("{rel_url}index.html")
-                } else if rel_url.ends_with(".html") || rel_url.ends_with(".htm") {
-                    rel_url.to_string()
-                } else {
-                    format!("{rel_url}/index.html")
-                };
+                let candidate_url = url_translation_key(rel_url);
 
                 if let Some(modified_url) = urlmap.get(&candidate_url) {
                     parsed.set_path(&filepath_to_output_url(modified_url));
@@ -384,9 +479,13 @@ impl<'a> RoseyPage<'a> {
             );
             return;
         };
+        let rtl = is_rtl_locale(locale_key, self.directions);
+        let writes_dir = self.writes_dir(rtl);
+        let mut overlong = vec![];
+        let mut warnings = BTreeSet::new();
         for edit in self.edits.iter() {
             match edit {
-                RoseyEdit::Content(key, original, node) => {
+                RoseyEdit::Content(key, original, original_attributes, node) => {
                     node.children().for_each(|child| {
                         child.detach();
                     });
@@ -395,40 +494,118 @@ impl<'a> RoseyPage<'a> {
                     let should_prevent_wrap =
                         UNSUPPORTED_WRAP_ELEMENTS.contains(&&element_data.name.local[..]);
 
-                    if let Some(content) = translation.get(key) {
+                    let count = element_data
+                        .attributes
+                        .borrow()
+                        .get(format!("{}-count", self.tag))
+                        .map(String::from);
+                    let content = match &count {
+                        Some(count) => translation.get_plural(key, locale_key, count),
+                        None => translation.get(key).cloned(),
+                    };
+                    let args = valid_attribute(
+                        &mut warnings,
+                        element_message_args(element_data, &self.tag, key),
+                    );
+                    let content = match (content, args) {
+                        (Some(content), Some(mut args)) => {
+                            if let Some(count) = &count {
+                                args.entry("count").or_insert_with(|| {
+                                    serde_json::from_str(count)
+                                        .unwrap_or_else(|_| Value::String(count.clone()))
+                                });
+                            }
+                            Some(format_message(&content, locale_key, &args).unwrap_or(content))
+                        }
+                        (content, _) => content,
+                    };
+                    if let (Some(content), Some(max_length)) = (
+                        &content,
+                        valid_attribute(
+                            &mut warnings,
+                            element_max_length(element_data, &self.tag, self.max_lengths),
+                        ),
+                    ) {
+                        overlong.extend(overlong_warning(locale_key, key, content, max_length));
+                    }
+
+                    // An explicit `data-rosey-dir` wins, otherwise text that only runs
+                    // against the direction of the page is left to the browser to detect.
+                    // Pages with the direction of the source keep the dir they were given.
+                    let dir_override = element_data
+                        .attributes
+                        .borrow()
+                        .get(format!("{}-dir", self.tag))
+                        .map(String::from);
+                    let text = content.as_ref().unwrap_or(original);
+                    let dir = if writes_dir {
+                        dir_override
+                            .or_else(|| original_attributes.dir.clone())
+                            .or_else(|| is_opposite_direction(text, rtl).then(|| "auto".into()))
+                    } else {
+                        original_attributes.dir.clone().or(dir_override)
+                    };
+
+                    // Breaks marked with `<wbr>` or a zero-width space need the
+                    // browser to stop breaking lines within words on its own
+                    let keep_all = content.is_some()
+                        && self.should_wrap
+                        && !should_prevent_wrap
+                        && self.wrap_mode != "span";
+                    let (style, class) = match (keep_all, self.wrap_class) {
+                        (true, Some(wrap_class)) => (
+                            original_attributes.style.clone(),
+                            Some(match &original_attributes.class {
+                                Some(class) => format!("{class} {wrap_class}"),
+                                None => wrap_class.clone(),
+                            }),
+                        ),
+                        (true, None) => (
+                            Some(keep_all_style(original_attributes.style.as_deref())),
+                            original_attributes.class.clone(),
+                        ),
+                        (false, _) => (
+                            original_attributes.style.clone(),
+                            original_attributes.class.clone(),
+                        ),
+                    };
+                    {
+                        let mut attributes = element_data.attributes.borrow_mut();
+                        for (name, value) in [("dir", dir), ("style", style), ("class", class)] {
+                            match value {
+                                Some(value) => attributes.insert(name, value),
+                                None => attributes.remove(name),
+                            };
+                        }
+                    }
+
+                    if let Some(content) = content {
                         let content = if content.contains('<') {
                             let mut rewriter = TranslationRewriter::new(
                                 &self.images_source,
                                 locale_key,
+                                &self.locale_chain,
                                 &self.default_language,
                                 self.translations,
                                 &self.tag,
                                 self.should_wrap,
                                 self.wrap_class,
+                                self.wrap_mode,
                             );
                             let mut tokenizer =
                                 Tokenizer::new(&mut rewriter, TokenizerOpts::default());
                             let mut buffer = BufferQueue::new();
-                            buffer.push_back(StrTendril::from_str(content).unwrap());
+                            buffer.push_back(StrTendril::from_str(&content).unwrap());
                             let _ = tokenizer.feed(&mut buffer);
                             tokenizer.end();
                             rewriter.finish()
                         } else if self.should_wrap && !should_prevent_wrap {
-                            content
-                                .as_str()
-                                .segment_str()
-                                .map(|segment| {
-                                    if segment.trim().is_empty() {
-                                        segment.to_string()
-                                    } else if let Some(class) = self.wrap_class {
-                                        format!("<span class=\"{class}\">{segment}</span>")
-                                    } else {
-                                        format!(
-                                            "<span style=\"white-space: nowrap;\">{segment}</span>"
-                                        )
-                                    }
-                                })
-                                .collect()
+                            wrap_words(&content, self.wrap_mode, self.wrap_class)
+                        } else if let Some(isolated) = (!should_prevent_wrap)
+                            .then(|| isolate_opposite_runs(&content, rtl))
+                            .flatten()
+                        {
+                            isolated
                         } else {
                             content.clone()
                         };
@@ -438,9 +615,17 @@ impl<'a> RoseyPage<'a> {
                     }
                 }
                 RoseyEdit::Attribute(key, attr, original, node) => {
-                    let mut attributes = node.as_element().unwrap().attributes.borrow_mut();
+                    let element_data = node.as_element().unwrap();
+                    let max_length = valid_attribute(
+                        &mut warnings,
+                        attribute_max_length(element_data, &self.tag, attr),
+                    );
+                    let mut attributes = element_data.attributes.borrow_mut();
 
                     if let Some(value) = translation.get(key) {
+                        if let Some(max_length) = max_length {
+                            overlong.extend(overlong_warning(locale_key, key, value, max_length));
+                        }
                         attributes.remove(attr.as_str());
                         attributes.insert(attr.as_str(), value.clone());
                     } else {
@@ -450,16 +635,26 @@ impl<'a> RoseyPage<'a> {
                         }
                     }
                 }
+                RoseyEdit::Format(format, value, original, node) => {
+                    node.children().for_each(|child| {
+                        child.detach();
+                    });
+
+                    let text =
+                        format_value(locale_key, format, value).unwrap_or_else(|| original.clone());
+                    node.append(NodeRef::new_text(text));
+                }
             }
         }
+        self.overlong.append(&mut overlong);
+        self.warnings.append(&mut warnings);
     }
 
     pub fn rewrite_assets(&mut self) {
-        let locale_key = self.get_locale_key();
         for (attr, original, node) in self.assets.iter() {
             let mut attributes = node.as_element().unwrap().attributes.borrow_mut();
             if let Some(translated_asset) =
-                get_translated_asset(original, &self.images_source, locale_key)
+                get_translated_asset(original, &self.images_source, &self.locale_chain)
             {
                 attributes.insert(attr.as_str(), format!("/{translated_asset}"));
             } else {
@@ -469,17 +664,16 @@ impl<'a> RoseyPage<'a> {
     }
 
     pub fn rewrite_image_tags(&mut self) {
-        let locale_key = self.get_locale_key();
         for (original_src, original_srcset, img, explicit_attrs) in self.image_tags.iter() {
             let mut attributes = img.as_element().unwrap().attributes.borrow_mut();
-            let has_explicit_src = explicit_attrs.as_ref().map(|attrs| {
-                return attrs.contains_key("src")
-            }).unwrap_or(false);
+            let has_explicit_src = explicit_attrs
+                .as_ref()
+                .is_some_and(|attrs| attrs.contains_key("src"));
 
             if !has_explicit_src {
                 if let Some(original) = original_src {
                     if let Some(translated_asset) =
-                        get_translated_asset(original, &self.images_source, locale_key)
+                        get_translated_asset(original, &self.images_source, &self.locale_chain)
                     {
                         eprintln!("rewrite_image_tags src: {}, translated_asset: {}", original, translated_asset);
                         attributes.insert("src", format!("/{translated_asset}"));
@@ -489,9 +683,9 @@ impl<'a> RoseyPage<'a> {
                 }
             }
 
-            let has_explicit_srcset = explicit_attrs.as_ref().map(|attrs| {
-                return attrs.contains_key("srcset")
-            }).unwrap_or(false);
+            let has_explicit_srcset = explicit_attrs
+                .as_ref()
+                .is_some_and(|attrs| attrs.contains_key("srcset"));
 
             if !has_explicit_srcset {
                 if let Some(original) = original_srcset {
@@ -503,9 +697,11 @@ impl<'a> RoseyPage<'a> {
                         })
                         .map(|(src, width, original)| {
                             if let (Some(src), Some(width)) = (src, width) {
-                                if let Some(translated_src) =
-                                    get_translated_asset(src, &self.images_source, locale_key)
-                                {
+                                if let Some(translated_src) = get_translated_asset(
+                                    src,
+                                    &self.images_source,
+                                    &self.locale_chain,
+                                ) {
                                     return format!("/{} {}", translated_src, width);
                                 }
                             }
@@ -536,6 +732,11 @@ impl<'a> RoseyPage<'a> {
             self.dom.select_first("html").unwrap()
         };
         self.html_tag = Some(html.as_node().clone());
+        self.html_dir = html.attributes.borrow().get("dir").map(String::from);
+        self.source_rtl = match self.html_dir.as_deref() {
+            Some(dir) => dir == "rtl",
+            None => is_rtl_locale(&self.default_language, self.directions),
+        };
 
         let head = if let Ok(head) = self.dom.select_first("head") {
             head
@@ -578,6 +779,22 @@ impl<'a> RoseyPage<'a> {
         };
         meta_node.insert_after(NodeRef::new_text(&indentation));
 
+        let mut attributes = BTreeMap::new();
+        attributes.insert(
+            ExpandedName::new("", "rel"),
+            Attribute {
+                prefix: None,
+                value: String::from("alternate"),
+            },
+        );
+        let x_default_node = NodeRef::new_element(
+            QualName::new(None, ns!(html), local_name!("link")),
+            attributes,
+        );
+        self.x_default_tag = Some(x_default_node.clone());
+        meta_node.insert_after(x_default_node);
+        meta_node.insert_after(NodeRef::new_text(&indentation));
+
         for _i in 0..=self.translations.len() {
             let mut attributes = BTreeMap::new();
             attributes.insert(
@@ -595,6 +812,19 @@ impl<'a> RoseyPage<'a> {
             meta_node.insert_after(node.clone());
             meta_node.insert_after(NodeRef::new_text(&indentation));
         }
+
+        for link in self.dom.select("link[rel=canonical][href]").unwrap() {
+            let attributes = link.attributes.borrow();
+            let href = attributes.get("href").unwrap();
+            self.canonical_tags
+                .push((href.to_string(), link.as_node().clone()));
+        }
+    }
+
+    // Left-to-right pages are left with the directions they were written with,
+    // unless they're translated from a right-to-left source
+    fn writes_dir(&self, rtl: bool) -> bool {
+        rtl || (rtl != self.source_rtl && self.get_locale_key() != self.default_language)
     }
 
     pub fn rewrite_meta_tags(
@@ -608,6 +838,16 @@ impl<'a> RoseyPage<'a> {
         let mut attributes = html_tag.as_element().unwrap().attributes.borrow_mut();
         attributes.remove("lang");
         attributes.insert("lang", locale_key.to_string());
+        let rtl = is_rtl_locale(locale_key, self.directions);
+        let dir = match (self.writes_dir(rtl), rtl) {
+            (true, true) => Some("rtl".to_string()),
+            (true, false) => Some("ltr".to_string()),
+            (false, _) => self.html_dir.clone(),
+        };
+        match dir {
+            Some(dir) => attributes.insert("dir", dir),
+            None => attributes.remove("dir"),
+        };
 
         let meta_tag = self.meta_tag.as_ref().unwrap();
         let mut attributes = meta_tag.as_element().unwrap().attributes.borrow_mut();
@@ -617,8 +857,8 @@ impl<'a> RoseyPage<'a> {
 
         for (i, key) in self
             .translations
-            .iter()
-            .map(|(key, _)| key.as_str())
+            .keys()
+            .map(|key| key.as_str())
             .chain(std::iter::once(&self.default_language[..]))
             .enumerate()
         {
@@ -654,6 +894,78 @@ impl<'a> RoseyPage<'a> {
                 attributes.insert("href", output_href);
             }
         }
+
+        let x_default_href = if self.default_language_at_root {
+            let translated_path = url_translations
+                .get(&self.default_language)
+                .and_then(|t| t.get(&original_relative_path.to_slash_lossy()))
+                .map(|p| filepath_to_output_url(p))
+                .unwrap_or_else(|| {
+                    filepath_to_output_url(&original_relative_path.to_slash_lossy())
+                });
+            format!("{0}/{translated_path}", self.base_url)
+        } else {
+            format!(
+                "{0}/{1}",
+                self.base_url,
+                filepath_to_output_url(&original_relative_path.to_slash_lossy())
+            )
+        };
+        let x_default_tag = self.x_default_tag.as_ref().unwrap();
+        let mut attributes = x_default_tag.as_element().unwrap().attributes.borrow_mut();
+        attributes.insert("hreflang", String::from("x-default"));
+        attributes.insert("href", x_default_href);
+
+        let locale_urls = url_translations.get(locale_key);
+        for (original, node) in &self.canonical_tags {
+            let mut attributes = node.as_element().unwrap().attributes.borrow_mut();
+            let output_href = self
+                .localize_url(original, locale_key, locale_urls)
+                .unwrap_or_else(|| original.clone());
+            attributes.insert("href", output_href);
+        }
+    }
+
+    // Rewrites a URL on this site to its translated, locale-prefixed URL.
+    // URLs that are already within a locale are left alone.
+    fn localize_url(
+        &self,
+        original: &str,
+        locale_key: &str,
+        url_translations: Option<&RoseyTranslation>,
+    ) -> Option<String> {
+        let site_url = Url::parse(&self.base_url)
+            .unwrap_or_else(|_| Url::parse("https://example.com").unwrap());
+        let mut parsed = site_url.join(original).ok()?;
+        if parsed.host_str() != site_url.host_str() {
+            return None;
+        }
+
+        let rel_url = parsed.path().trim_start_matches('/').to_string();
+        if self
+            .translations
+            .keys()
+            .chain(std::iter::once(&self.default_language))
+            .any(|key| rel_url.starts_with(&format!("{key}/")) || rel_url == *key)
+        {
+            return None;
+        }
+
+        let translated_path = url_translations
+            .and_then(|urlmap| urlmap.get(&url_translation_key(&rel_url)))
+            .map(|p| filepath_to_output_url(p))
+            .unwrap_or(rel_url);
+        if locale_key == self.default_language && self.default_language_at_root {
+            parsed.set_path(&format!("/{translated_path}"));
+        } else {
+            parsed.set_path(&format!("/{locale_key}/{translated_path}"));
+        }
+
+        if Url::parse(original).is_ok() {
+            Some(parsed.to_string())
+        } else {
+            Some(parsed[url::Position::BeforePath..].to_string())
+        }
     }
 
     fn process_image_tags(&mut self) {
@@ -778,8 +1090,29 @@ impl<'a> RoseyPage<'a> {
                 }
 
                 let inner_html: String = node.children().map(|child| child.to_string()).collect();
-                self.edits
-                    .push(RoseyEdit::Content(key, inner_html, node.clone()));
+                self.edits.push(RoseyEdit::Content(
+                    key,
+                    inner_html,
+                    RoseyOriginalAttributes {
+                        dir: attributes.get("dir").map(String::from),
+                        style: attributes.get("style").map(String::from),
+                        class: attributes.get("class").map(String::from),
+                    },
+                    node.clone(),
+                ));
+            } else if let Some(format) = attributes.get(format!("{}-format", self.tag)) {
+                let value = attributes
+                    .get(format!("{}-value", self.tag))
+                    .or_else(|| attributes.get("datetime"))
+                    .map(String::from)
+                    .unwrap_or_else(|| node.text_contents());
+                let inner_html: String = node.children().map(|child| child.to_string()).collect();
+                self.edits.push(RoseyEdit::Format(
+                    format.to_string(),
+                    value,
+                    inner_html,
+                    node.clone(),
+                ));
             }
 
             if let Some(attrs_map) = attributes.get(format!("{}-attrs-explicit", self.tag)) {
@@ -800,42 +1133,78 @@ impl<'a> RoseyPage<'a> {
         }
     }
 
-    pub fn output_file(&self, output_path: &Path) {
+    pub fn output_file(&self, output_path: &Path) -> Result<(), RoseyError> {
         if let Some(parent) = output_path.parent() {
-            create_dir_all(parent).unwrap();
+            create_dir_all(parent).map_err(|e| RoseyError::io(parent, e))?;
         }
 
-        if let Ok(file) = File::create(output_path) {
-            let writer = BufWriter::new(file);
-            let mut serializer = RoseySerializer::new(writer);
-            if Serialize::serialize(
-                &self.dom,
-                &mut serializer,
-                html5ever::serialize::TraversalScope::IncludeNode,
-            )
-            .is_err()
-            {
-                eprintln!("Failed to write: {output_path:?}")
-            }
-        }
+        let file = File::create(output_path).map_err(|e| RoseyError::io(output_path, e))?;
+        let mut writer = BufWriter::new(file);
+        let mut serializer = RoseySerializer::new(&mut writer);
+        Serialize::serialize(
+            &self.dom,
+            &mut serializer,
+            html5ever::serialize::TraversalScope::IncludeNode,
+        )
+        .and_then(|_| writer.flush())
+        .map_err(|e| RoseyError::io(output_path, e))
+    }
+}
+
+fn url_translation_key(rel_url: &str) -> String {
+    if rel_url.is_empty() || rel_url.ends_with('/') {
+        format!("{rel_url}index.html")
+    } else if rel_url.ends_with(".html") || rel_url.ends_with(".htm") {
+        rel_url.to_string()
+    } else {
+        format!("{rel_url}/index.html")
     }
 }
 
-pub fn get_translated_asset(original: &str, source: &Path, locale_key: &str) -> Option<String> {
+pub fn get_translated_asset(original: &str, source: &Path, locales: &[String]) -> Option<String> {
     let original_path = Path::new(original);
     if let Some(ext) = original_path.extension() {
-        let mut translated_asset = PathBuf::from(original_path);
-        translated_asset.set_extension(format!("{locale_key}.{}", ext.to_str().unwrap()));
-        if let Ok(stripped_path) = translated_asset.strip_prefix(MAIN_SEPARATOR.to_string()) {
-            translated_asset = stripped_path.to_path_buf();
-        }
+        for locale_key in locales {
+            let mut translated_asset = PathBuf::from(original_path);
+            translated_asset.set_extension(format!("{locale_key}.{}", ext.to_str().unwrap()));
+            if let Ok(stripped_path) = translated_asset.strip_prefix(MAIN_SEPARATOR.to_string()) {
+                translated_asset = stripped_path.to_path_buf();
+            }
 
-        let translated_path = source.join(&translated_asset);
+            let translated_path = source.join(&translated_asset);
 
-        if translated_path.exists() {
-            let src = translated_asset.to_str().unwrap().replace('\\', "/");
-            return Some(src);
+            if translated_path.exists() {
+                let src = translated_asset.to_str().unwrap().replace('\\', "/");
+                return Some(src);
+            }
         }
     }
     None
 }
+
+fn keep_all_style(style: Option<&str>) -> String {
+    match style.map(|style| style.trim().trim_end_matches(';')) {
+        Some(style) if !style.is_empty() => format!("{style}; word-break: keep-all;"),
+        _ => "word-break: keep-all;".to_string(),
+    }
+}
+
+// Invalid attributes are reported once per page, and otherwise ignored
+fn valid_attribute<T>(
+    warnings: &mut BTreeSet<String>,
+    value: Result<Option<T>, String>,
+) -> Option<T> {
+    value.unwrap_or_else(|warning| {
+        warnings.insert(warning);
+        None
+    })
+}
+
+fn overlong_warning(locale: &str, key: &str, value: &str, max_length: u32) -> Option<String> {
+    let length = text_length(value);
+    (length > max_length as usize).then(|| {
+        format!(
+            "{locale} translation {key:?} is {length} characters, longer than its maximum of {max_length}"
+        )
+    })
+}
@@ -1,13 +1,12 @@
 use std::{collections::BTreeMap, fmt::Write as FmtWrite, io::Write, path::Path};
 
-use charabia::Segment;
 use html5ever::{
     serialize::{HtmlSerializer, SerializeOpts, Serializer},
     tokenizer::{TokenSink, TokenSinkResult},
     QualName,
 };
 
-use crate::RoseyTranslation;
+use crate::{wrap::wrap_words, RoseyTranslation};
 
 use super::get_translated_asset;
 
@@ -19,32 +18,39 @@ pub struct TranslationRewriter<'a> {
     result: String,
     images_source: &'a Path,
     locale_key: &'a str,
+    locales: &'a [String],
     default_language: &'a str,
     translations: &'a BTreeMap<String, RoseyTranslation>,
     tag: &'a str,
     should_wrap: bool,
     wrap_class: &'a Option<String>,
+    wrap_mode: &'a str,
 }
 
 impl<'a> TranslationRewriter<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         images_source: &'a Path,
         locale_key: &'a str,
+        locales: &'a [String],
         default_language: &'a str,
         translations: &'a BTreeMap<String, RoseyTranslation>,
         tag: &'a str,
         should_wrap: bool,
         wrap_class: &'a Option<String>,
+        wrap_mode: &'a str,
     ) -> Self {
         TranslationRewriter {
             result: String::new(),
             images_source,
             locale_key,
+            locales,
             default_language,
             translations,
             tag,
             should_wrap,
             wrap_class,
+            wrap_mode,
         }
     }
 
@@ -92,7 +98,7 @@ impl<'a> TokenSink for &mut TranslationRewriter<'a> {
                             if let Some(translated_asset) = get_translated_asset(
                                 &attr.value,
                                 self.images_source,
-                                self.locale_key,
+                                self.locales,
                             ) {
                                 write!(self.result, "/{translated_asset}").expect("Failed to rewrite content - custom asset attribute");
                             } else {
@@ -103,7 +109,7 @@ impl<'a> TokenSink for &mut TranslationRewriter<'a> {
                             if let Some(translated_asset) = get_translated_asset(
                                 &attr.value,
                                 self.images_source,
-                                self.locale_key,
+                                self.locales,
                             ) {
                                 write!(self.result, "/{translated_asset}").expect("Failed to rewrite content - asset attribute");
                             } else {
@@ -114,7 +120,7 @@ impl<'a> TokenSink for &mut TranslationRewriter<'a> {
                             if let Some(translated_asset) = get_translated_asset(
                                 &attr.value,
                                 self.images_source,
-                                self.locale_key,
+                                self.locales,
                             ) {
                                 write!(self.result, "/{translated_asset}").expect("Failed to rewrite content - download link");
                             } else {
@@ -151,7 +157,7 @@ impl<'a> TokenSink for &mut TranslationRewriter<'a> {
                                         if let Some(translated_src) = get_translated_asset(
                                             src,
                                             self.images_source,
-                                            self.locale_key,
+                                            self.locales,
                                         ) {
                                             return format!("/{} {}", translated_src, width);
                                         }
@@ -180,20 +186,8 @@ impl<'a> TokenSink for &mut TranslationRewriter<'a> {
                 }
             }
             html5ever::tokenizer::Token::CharacterTokens(tendril) => if self.should_wrap{
-                let tendril: &str = &tendril;
-                tendril
-                    .segment_str()
-                    .for_each(|segment| {
-                        if segment.trim().is_empty() {
-                            self.result.push_str(segment);
-                        } else if let Some(class) = self.wrap_class {
-                            write!(&mut self.result, "<span class=\"{class}\">{segment}</span>")
-                                .expect("Failed to segment inner html");
-                        } else {
-                            write!(&mut self.result, "<span style=\"white-space: nowrap;\">{segment}</span>")
-                                .expect("Failed to segment inner html");
-                        }
-                    })
+                self.result
+                    .push_str(&wrap_words(&tendril, self.wrap_mode, self.wrap_class));
             } else {
                 self.result.push_str(&tendril)
             }
@@ -1,6 +1,13 @@
-use crate::RoseyTranslation;
+use crate::{
+    runners::{array_item_namespace, data_file_extension, parse_data_file, schema_rule_matches},
+    RoseyError, RoseyTranslation,
+};
 
-use super::RoseyBuilder;
+use super::{
+    cache::{hash_content, RoseyBuildManifestFile},
+    yaml::translate_yaml,
+    RoseyBuilder,
+};
 
 use std::{
     fs::read_to_string,
@@ -8,57 +15,113 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use path_slash::PathExt as _;
 use rayon::prelude::*;
-use serde_json::Value;
+use serde_json::{Map, Value};
+use toml_edit::{DocumentMut, Formatted, Item, Table};
+
+// Schema files such as `titles.rosey.yml` aren't copied into the output
+pub fn is_schema_file(path: &Path) -> bool {
+    data_file_extension(path).is_some()
+        && path
+            .file_stem()
+            .and_then(|stem| Path::new(stem).extension())
+            .is_some_and(|ext| ext == "rosey")
+}
+
+// Data files with a schema are translated, and output by `process_json_file`
+// rather than copied as assets
+pub fn data_file_schema(path: &Path) -> Option<PathBuf> {
+    let extension = data_file_extension(path)?;
+    let mut schema_path = PathBuf::from(path);
+    schema_path.set_extension(format!("rosey.{extension}"));
+    schema_path.exists().then_some(schema_path)
+}
 
 impl RoseyBuilder {
-    pub fn process_json_file(&self, file: &Path) {
+    pub fn process_json_file(&self, file: &Path) -> Result<(), RoseyError> {
         let config = &self.options.config;
-        let mut schema_path = PathBuf::from(file);
-        schema_path.set_extension("rosey.json");
-        if !schema_path.exists() {
-            return;
-        }
-
-        let content = read_to_string(file).unwrap();
-        let source = serde_json::from_str::<Value>(&content);
-        let schema = serde_json::from_str::<Value>(&read_to_string(&schema_path).unwrap());
+        let (Some(extension), Some(schema_path)) =
+            (data_file_extension(file), data_file_schema(file))
+        else {
+            return Ok(());
+        };
 
-        if source.is_err() {
-            eprintln!("Failed to parse {file:?}");
-            return;
-        }
+        let source_folder = &config.source;
+        let relative_path = file.strip_prefix(source_folder).unwrap();
+        let relative_key = relative_path.to_slash_lossy().to_string();
 
-        if schema.is_err() {
-            eprintln!("Failed to parse {schema_path:?}");
-            return;
+        let content = read_to_string(file).map_err(|e| RoseyError::io(file, e))?;
+        let schema_content =
+            read_to_string(&schema_path).map_err(|e| RoseyError::io(&schema_path, e))?;
+        let hash = hash_content(format!("{content}\0{schema_content}").as_bytes());
+        if self.reuse_cached_file(&relative_key, &hash, |_| self.locale_hashes()) {
+            return Ok(());
         }
 
-        let source = source.unwrap();
-        let schema = schema.unwrap();
+        let source = parse_data_file(file, &content)?;
+        let schema = parse_data_file(&schema_path, &schema_content)?;
+        let rule_matches = schema_rule_matches(&source, &schema, &relative_key, &self.warnings);
 
-        let source_folder = &config.source;
-        let relative_path = file.strip_prefix(source_folder).unwrap();
+        self.translations
+            .par_iter()
+            .try_for_each(|(key, translation)| {
+                let mut source = source.clone();
+                match &rule_matches {
+                    Some(matches) => {
+                        for matched in matches {
+                            if let (Some(Value::String(source_value)), Some(value)) = (
+                                source.pointer_mut(&matched.pointer),
+                                translation.get(&matched.key),
+                            ) {
+                                *source_value = value.clone();
+                            }
+                        }
+                    }
+                    None => self.process_json_node(
+                        &relative_key,
+                        &mut source,
+                        &schema,
+                        None,
+                        translation,
+                    ),
+                }
 
-        self.output_file(&config.default_language, relative_path, content);
+                let content = match extension {
+                    "yml" | "yaml" => translate_yaml(&content, &source),
+                    "toml" => translate_toml(&content, &source),
+                    _ => serde_json::to_string_pretty(&source)
+                        .map_err(|e| RoseyError::parse(file, e))?,
+                };
+                self.output_file(key, relative_path, content)
+            })?;
 
-        self.translations.par_iter().for_each(|(key, translation)| {
-            let mut source = source.clone();
-            RoseyBuilder::process_json_node(&mut source, &schema, None, translation);
+        self.output_file(&config.default_language, relative_path, content)?;
 
-            let content = serde_json::to_string_pretty(&source).unwrap();
-            self.output_file(key, relative_path, content);
+        self.record_file(&relative_key, || RoseyBuildManifestFile {
+            hash,
+            translations: self.locale_hashes(),
+            outputs: self
+                .translations
+                .keys()
+                .chain(std::iter::once(&config.default_language))
+                .map(|locale| format!("{locale}/{relative_key}"))
+                .collect(),
+            ..Default::default()
         });
+        Ok(())
     }
 
     fn process_json_node(
+        &self,
+        file: &str,
         source: &mut Value,
         schema: &Value,
         namespace: Option<String>,
         translation: &RoseyTranslation,
     ) {
         if discriminant(source) != discriminant(schema) {
-            eprintln!("Schema mismatch");
+            self.warnings.warn(file, "Schema mismatch");
             return;
         }
 
@@ -89,7 +152,8 @@ impl RoseyBuilder {
                     } else if let (Some(source_value), Some(schema_value)) =
                         (source_map.get_mut(key), schema_map.get(key))
                     {
-                        RoseyBuilder::process_json_node(
+                        self.process_json_node(
+                            file,
                             source_value,
                             schema_value,
                             Some(namespace.clone()),
@@ -104,6 +168,33 @@ impl RoseyBuilder {
                 let mut key = None;
 
                 match schema_array.first() {
+                    Some(Value::String(schema_value))
+                        if schema_value.trim().starts_with("rosey-array-ns:") =>
+                    {
+                        let field = schema_value.trim().strip_prefix("rosey-array-ns:").unwrap();
+                        let Some(item_schema) = schema_array.get(1) else {
+                            let message = format!(
+                                "Schema mismatch in array: Expected a schema after {schema_value}"
+                            );
+                            self.warnings.warn(file, message);
+                            return;
+                        };
+
+                        source_array
+                            .iter_mut()
+                            .enumerate()
+                            .for_each(|(index, source_value)| {
+                                let item_namespace =
+                                    array_item_namespace(source_value, field, index);
+                                self.process_json_node(
+                                    file,
+                                    source_value,
+                                    item_schema,
+                                    Some(format!("{namespace}{item_namespace}.")),
+                                    translation,
+                                )
+                            })
+                    }
                     Some(Value::String(schema_value)) => {
                         schema_value.trim().split('|').for_each(|part| {
                             if part == "rosey-array-ns" {
@@ -126,22 +217,80 @@ impl RoseyBuilder {
                                     *source_value = value.clone();
                                 }
                             } else {
-                                eprintln!("Schema mismatch in array: Expected String");
+                                self.warnings
+                                    .warn(file, "Schema mismatch in array: Expected String");
                             }
                         })
                     }
                     Some(schema_value) => source_array.iter_mut().for_each(|source_value| {
-                        RoseyBuilder::process_json_node(
-                            source_value,
-                            schema_value,
-                            None,
-                            translation,
-                        )
+                        self.process_json_node(file, source_value, schema_value, None, translation)
                     }),
-                    _ => eprintln!("Schema mismatch in array: Expected String|Object"),
+                    _ => self
+                        .warnings
+                        .warn(file, "Schema mismatch in array: Expected String|Object"),
                 }
             }
             _ => (),
         }
     }
 }
+
+// Writes translated strings back into the original TOML document,
+// so that its comments and formatting are kept
+fn translate_toml(original: &str, translated: &Value) -> String {
+    let Ok(mut document) = original.parse::<DocumentMut>() else {
+        return toml::to_string_pretty(translated).unwrap_or_default();
+    };
+    if let Value::Object(translated) = translated {
+        translate_toml_table(document.as_table_mut(), translated);
+    }
+    document.to_string()
+}
+
+fn translate_toml_table(table: &mut Table, translated: &Map<String, Value>) {
+    for (key, item) in table.iter_mut() {
+        if let Some(value) = translated.get(key.get()) {
+            translate_toml_item(item, value);
+        }
+    }
+}
+
+fn translate_toml_item(item: &mut Item, translated: &Value) {
+    match (item, translated) {
+        (Item::Value(value), translated) => translate_toml_value(value, translated),
+        (Item::Table(table), Value::Object(translated)) => translate_toml_table(table, translated),
+        (Item::ArrayOfTables(tables), Value::Array(translated)) => {
+            for (table, translated) in tables.iter_mut().zip(translated) {
+                if let Value::Object(translated) = translated {
+                    translate_toml_table(table, translated);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn translate_toml_value(value: &mut toml_edit::Value, translated: &Value) {
+    match (value, translated) {
+        (toml_edit::Value::String(value), Value::String(translated))
+            if value.value() != translated =>
+        {
+            let decor = value.decor().clone();
+            *value = Formatted::new(translated.clone());
+            *value.decor_mut() = decor;
+        }
+        (toml_edit::Value::Array(array), Value::Array(translated)) => {
+            for (value, translated) in array.iter_mut().zip(translated) {
+                translate_toml_value(value, translated);
+            }
+        }
+        (toml_edit::Value::InlineTable(table), Value::Object(translated)) => {
+            for (key, value) in table.iter_mut() {
+                if let Some(translated) = translated.get(key.get()) {
+                    translate_toml_value(value, translated);
+                }
+            }
+        }
+        _ => {}
+    }
+}
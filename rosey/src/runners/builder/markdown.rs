@@ -0,0 +1,112 @@
+use crate::{
+    markdown::{markdown_keys, translate_markdown},
+    RoseyError,
+};
+
+use super::{
+    cache::{hash_content, RoseyBuildManifestFile},
+    RoseyBuilder,
+};
+
+use std::{fs::read, path::Path};
+
+use path_slash::PathExt as _;
+use rayon::prelude::*;
+
+// Markdown files are written by process_markdown_file rather than copied as assets
+pub fn is_markdown_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("md" | "txt")
+    )
+}
+
+impl RoseyBuilder {
+    pub fn process_markdown_file(&self, file: &Path) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let source_folder = &config.source;
+        let dest_folder = &config.dest;
+        let relative_path = file.strip_prefix(source_folder).unwrap();
+        let relative_key = relative_path.to_slash_lossy().to_string();
+
+        let bytes = read(file).map_err(|e| RoseyError::io(file, e))?;
+        let hash = hash_content(&bytes);
+        // Files that aren't UTF-8 text can't contain markers, so are copied as they are
+        let content = String::from_utf8(bytes);
+        let keys = content
+            .as_deref()
+            .map(|content| markdown_keys(content).into_iter().map(|(key, _)| key))
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        //If the file is already in a locale folder, then output it only for that locale
+        let locale_overwrite = self.find_locale_overwrite(file);
+        let locales = match locale_overwrite {
+            Some(key) => vec![key],
+            None => self
+                .translations
+                .keys()
+                .filter(|locale| !source_folder.join(locale).join(relative_path).exists())
+                .collect::<Vec<_>>(),
+        };
+        let base_rebuilt = locale_overwrite.is_some_and(|key| {
+            self.was_rebuilt(&relative_path.strip_prefix(key).unwrap().to_slash_lossy())
+        });
+        if !base_rebuilt
+            && self.reuse_cached_file(&relative_key, &hash, |keys| {
+                self.translation_hashes(keys, &locales)
+            })
+        {
+            return Ok(());
+        }
+
+        let mut outputs = vec![dest_folder.join(relative_path)];
+        match content {
+            Ok(content) if !keys.is_empty() => {
+                if let Some(locale) = locale_overwrite {
+                    let translation = &self.translations[locale];
+                    let output = translate_markdown(&content, |key| translation.get(key).cloned());
+                    self.output_file("", relative_path, output)?;
+                } else {
+                    let original = translate_markdown(&content, |_| None);
+                    self.output_file("", relative_path, original.clone())?;
+                    if !config.default_language_at_root {
+                        self.output_file(&config.default_language, relative_path, original)?;
+                        outputs.push(
+                            dest_folder
+                                .join(&config.default_language)
+                                .join(relative_path),
+                        );
+                    }
+
+                    locales.par_iter().try_for_each(|locale| {
+                        let translation = &self.translations[*locale];
+                        let output =
+                            translate_markdown(&content, |key| translation.get(key).cloned());
+                        self.output_file(locale, relative_path, output)
+                    })?;
+                    outputs.extend(
+                        locales
+                            .iter()
+                            .map(|locale| dest_folder.join(locale).join(relative_path)),
+                    );
+                }
+            }
+            Ok(content) => self.output_file("", relative_path, content)?,
+            Err(e) => {
+                let dest_file = dest_folder.join(relative_path);
+                std::fs::write(&dest_file, e.as_bytes())
+                    .map_err(|e| RoseyError::io(&dest_file, e))?;
+            }
+        }
+
+        self.record_file(&relative_key, || RoseyBuildManifestFile {
+            hash,
+            translations: self.translation_hashes(&keys, &locales),
+            keys,
+            outputs: self.output_paths(&outputs),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+use std::{collections::BTreeMap, fmt::Write, fs::read_to_string};
+
+use serde_json::{json, Value};
+
+use super::{html::filepath_to_output_url, RoseyBuilder};
+use crate::{runners::write_output, RoseyError};
+
+const REDIRECT_COOKIE: &str = "rosey_language";
+
+struct RoseyRedirect {
+    from: String,
+    targets: Vec<(String, String)>,
+}
+
+impl RoseyBuilder {
+    pub fn write_redirects(&self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let Some(formats) = &config.redirects else {
+            return Ok(());
+        };
+        if config.default_language_at_root {
+            return Ok(());
+        }
+
+        let redirects = self.redirect_rules();
+        let languages = self.redirect_languages();
+        for format in formats {
+            match format.as_str() {
+                "netlify" => {
+                    let output = self.source_file_with("_redirects", |output| {
+                        netlify_redirects(output, &redirects, &languages)
+                    })?;
+                    write_output(&config.dest.join("_redirects"), &output)?;
+
+                    let output = self.source_file_with("_headers", |output| {
+                        netlify_headers(output, &redirects)
+                    })?;
+                    write_output(&config.dest.join("_headers"), &output)?;
+                }
+                "vercel" => {
+                    let output = self.vercel_config(&redirects, &languages)?;
+                    write_output(&config.dest.join("vercel.json"), &output)?;
+                }
+                "nginx" => {
+                    let output = nginx_map(&redirects, &self.locale_lookup(), &languages);
+                    write_output(&config.dest.join("rosey-redirects.conf"), &output)?;
+                }
+                _ => unreachable!("Tried to write unknown redirect format."),
+            }
+        }
+        Ok(())
+    }
+
+    fn redirect_rules(&self) -> Vec<RoseyRedirect> {
+        let config = &self.options.config;
+        self.source_pages()
+            .iter()
+            .map(|path| {
+                let from = format!("/{}", filepath_to_output_url(path));
+                let targets = self
+                    .translations
+                    .keys()
+                    .filter(|locale| **locale != config.default_language)
+                    .chain(std::iter::once(&config.default_language))
+                    .map(|locale| {
+                        let to = format!("/{locale}/{}", self.translated_url(path, locale));
+                        (locale.clone(), to)
+                    })
+                    .collect();
+                RoseyRedirect { from, targets }
+            })
+            .collect()
+    }
+
+    // The language codes from the locale lookup that select each locale,
+    // most specific first.
+    fn redirect_languages(&self) -> BTreeMap<String, Vec<String>> {
+        let mut languages: BTreeMap<String, Vec<String>> = BTreeMap::default();
+        for (code, locale) in self.locale_lookup() {
+            if !code.contains('_') {
+                languages
+                    .entry(locale.to_string())
+                    .or_default()
+                    .push(code.to_lowercase());
+            }
+        }
+        for codes in languages.values_mut() {
+            codes.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+            codes.dedup();
+        }
+        languages
+    }
+
+    fn source_file_with(
+        &self,
+        file: &str,
+        append: impl FnOnce(&mut String),
+    ) -> Result<String, RoseyError> {
+        let source_file = self.options.config.source.join(file);
+        let mut output = if source_file.exists() {
+            let mut existing =
+                read_to_string(&source_file).map_err(|e| RoseyError::io(&source_file, e))?;
+            if !existing.ends_with('\n') {
+                existing.push('\n');
+            }
+            existing.push('\n');
+            existing
+        } else {
+            String::new()
+        };
+        append(&mut output);
+        Ok(output)
+    }
+
+    fn vercel_config(
+        &self,
+        redirects: &[RoseyRedirect],
+        languages: &BTreeMap<String, Vec<String>>,
+    ) -> Result<String, RoseyError> {
+        let config = &self.options.config;
+        let source_file = config.source.join("vercel.json");
+        let mut vercel = if source_file.exists() {
+            let existing =
+                read_to_string(&source_file).map_err(|e| RoseyError::io(&source_file, e))?;
+            serde_json::from_str::<Value>(&existing)
+                .map_err(|e| RoseyError::parse(&source_file, e))?
+        } else {
+            json!({})
+        };
+
+        let Some(vercel_object) = vercel.as_object_mut() else {
+            return Err(RoseyError::parse(&source_file, "expected a JSON object"));
+        };
+        let rules = vercel_object
+            .entry("redirects")
+            .or_insert_with(|| json!([]))
+            .as_array_mut()
+            .ok_or_else(|| RoseyError::parse(&source_file, "expected redirects to be an array"))?;
+
+        for RoseyRedirect { from, targets } in redirects {
+            for (locale, to) in targets.iter() {
+                if *locale == config.default_language {
+                    continue;
+                }
+                rules.push(json!({
+                    "source": from,
+                    "has": [{ "type": "cookie", "key": REDIRECT_COOKIE, "value": locale }],
+                    "destination": to,
+                    "permanent": false
+                }));
+            }
+            for (locale, to) in targets.iter() {
+                let Some(codes) = languages.get(locale) else {
+                    continue;
+                };
+                if *locale == config.default_language {
+                    rules.push(json!({
+                        "source": from,
+                        "destination": to,
+                        "permanent": false
+                    }));
+                    continue;
+                }
+                rules.push(json!({
+                    "source": from,
+                    "has": [{
+                        "type": "header",
+                        "key": "accept-language",
+                        "value": format!("(?i)^({})(?![a-z]).*", language_pattern(codes))
+                    }],
+                    "destination": to,
+                    "permanent": false
+                }));
+            }
+        }
+
+        let mut output = serde_json::to_string_pretty(&vercel).unwrap();
+        output.push('\n');
+        Ok(output)
+    }
+}
+
+fn language_pattern(codes: &[String]) -> String {
+    codes
+        .iter()
+        .map(|code| code.replace('-', "[-_]"))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+fn netlify_redirects(
+    output: &mut String,
+    redirects: &[RoseyRedirect],
+    languages: &BTreeMap<String, Vec<String>>,
+) {
+    output.push_str("# Language redirects generated by Rosey\n");
+    // Forced, as Netlify otherwise serves the HTML redirect page at the same path
+    for RoseyRedirect { from, targets } in redirects {
+        let (default, localized) = targets.split_last().unwrap();
+        for (locale, to) in localized {
+            if let Some(codes) = languages.get(locale) {
+                writeln!(output, "{from}  {to}  302!  Language={}", codes.join(","))
+                    .expect("Failed to write Netlify redirects");
+            }
+        }
+        writeln!(output, "{from}  {}  302!", default.1).expect("Failed to write Netlify redirects");
+    }
+}
+
+fn netlify_headers(output: &mut String, redirects: &[RoseyRedirect]) {
+    output.push_str("# Language redirect headers generated by Rosey\n");
+    for RoseyRedirect { from, .. } in redirects {
+        writeln!(output, "{from}\n  Vary: Accept-Language, Cookie")
+            .expect("Failed to write Netlify headers");
+    }
+}
+
+fn nginx_map(
+    redirects: &[RoseyRedirect],
+    lookup: &BTreeMap<String, &str>,
+    languages: &BTreeMap<String, Vec<String>>,
+) -> String {
+    let default_language = redirects
+        .first()
+        .and_then(|redirect| redirect.targets.last())
+        .map(|(locale, _)| locale.as_str())
+        .unwrap_or_default();
+
+    let mut output = String::from(
+        "# Language redirects generated by Rosey.\n\
+         # Include this file in your http block, and add the following to your server block:\n\
+         #   if ($rosey_redirect) { return 302 $rosey_redirect; }\n\n",
+    );
+
+    let mut write = |line: String| {
+        output.push_str(&line);
+        output.push('\n');
+    };
+
+    write(format!(
+        "map $cookie_{REDIRECT_COOKIE} $rosey_cookie_language {{"
+    ));
+    write("    default \"\";".to_string());
+    for (code, locale) in lookup {
+        write(format!("    \"{code}\" \"{locale}\";"));
+    }
+    write("}\n".to_string());
+
+    write("map $http_accept_language $rosey_accept_language {".to_string());
+    write(format!("    default \"{default_language}\";"));
+    let mut patterns = languages
+        .iter()
+        .flat_map(|(locale, codes)| codes.iter().map(move |code| (code, locale)))
+        .collect::<Vec<_>>();
+    patterns.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then(a.cmp(b)));
+    for (code, locale) in patterns {
+        write(format!(
+            "    \"~*^{}(?![a-z])\" \"{locale}\";",
+            code.replace('-', "[-_]")
+        ));
+    }
+    write("}\n".to_string());
+
+    write("map $rosey_cookie_language $rosey_language {".to_string());
+    write("    \"\" $rosey_accept_language;".to_string());
+    write("    default $rosey_cookie_language;".to_string());
+    write("}\n".to_string());
+
+    write("map \"$rosey_language:$uri\" $rosey_redirect {".to_string());
+    write("    default \"\";".to_string());
+    for RoseyRedirect { from, targets } in redirects {
+        for (locale, to) in targets {
+            write(format!("    \"{locale}:{from}\" \"{to}\";"));
+        }
+    }
+    write("}".to_string());
+
+    output
+}
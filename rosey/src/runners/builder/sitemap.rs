@@ -0,0 +1,225 @@
+use std::{fs::read_to_string, io::Cursor};
+
+use quick_xml::{
+    events::{BytesDecl, BytesText, Event},
+    Reader, Writer,
+};
+
+use super::{html::filepath_to_output_url, RoseyBuilder};
+use crate::{runners::write_output, RoseyError};
+
+const SITEMAP_DETAILS: [&str; 3] = ["lastmod", "changefreq", "priority"];
+
+#[derive(Default)]
+struct RoseySitemapEntry {
+    loc: String,
+    path: Option<String>,
+    details: Vec<(String, String)>,
+}
+
+impl RoseyBuilder {
+    pub fn write_sitemap(&self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        if !config.sitemap {
+            return Ok(());
+        }
+
+        let source_sitemap = config.source.join("sitemap.xml");
+        let entries = if source_sitemap.exists() {
+            let content =
+                read_to_string(&source_sitemap).map_err(|e| RoseyError::io(&source_sitemap, e))?;
+            match self.read_sitemap(&content) {
+                Ok(Some(entries)) => entries,
+                Ok(None) => {
+                    self.warnings.warn(
+                        "sitemap.xml",
+                        "Skipping sitemap, as it does not contain a <urlset>",
+                    );
+                    return Ok(());
+                }
+                Err(e) => return Err(RoseyError::parse(&source_sitemap, e)),
+            }
+        } else {
+            self.sitemap_pages()
+        };
+
+        let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+        self.write_urlset(&mut writer, &entries)
+            .expect("Failed to write sitemap");
+        let mut output = String::from_utf8(writer.into_inner().into_inner()).unwrap();
+        output.push('\n');
+
+        write_output(&config.dest.join("sitemap.xml"), &output)
+    }
+
+    fn sitemap_pages(&self) -> Vec<RoseySitemapEntry> {
+        self.source_pages()
+            .into_iter()
+            .map(|path| RoseySitemapEntry {
+                path: Some(path),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn read_sitemap(
+        &self,
+        content: &str,
+    ) -> Result<Option<Vec<RoseySitemapEntry>>, quick_xml::Error> {
+        let mut reader = Reader::from_str(content);
+        reader.config_mut().trim_text(true);
+
+        let mut entries = vec![];
+        let mut has_urlset = false;
+        let mut entry: Option<RoseySitemapEntry> = None;
+        let mut capture: Option<String> = None;
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                    match name.as_str() {
+                        "urlset" => has_urlset = true,
+                        "url" => entry = Some(RoseySitemapEntry::default()),
+                        _ if entry.is_some() => capture = Some(name),
+                        _ => {}
+                    }
+                }
+                Event::Text(e) => {
+                    if let (Some(name), Some(entry)) = (&capture, entry.as_mut()) {
+                        let text = e.unescape()?.trim().to_string();
+                        if name == "loc" {
+                            entry.loc = text;
+                        } else if SITEMAP_DETAILS.contains(&name.as_str()) {
+                            entry.details.push((name.clone(), text));
+                        }
+                    }
+                }
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"url" => {
+                        if let Some(mut entry) = entry.take() {
+                            entry.path = self.sitemap_source_path(&entry.loc);
+                            entries.push(entry);
+                        }
+                    }
+                    _ => capture = None,
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(has_urlset.then_some(entries))
+    }
+
+    // Finds the source page for a URL in the sitemap, if it belongs to this site
+    fn sitemap_source_path(&self, loc: &str) -> Option<String> {
+        let config = &self.options.config;
+        let path = loc.strip_prefix(config.base_url.as_str())?;
+        let path = path.split(['?', '#']).next().unwrap_or(path);
+        let path = path.trim_start_matches('/');
+
+        let candidates = if path.is_empty() || path.ends_with('/') {
+            vec![format!("{path}index.html")]
+        } else {
+            vec![path.to_string(), format!("{path}/index.html")]
+        };
+        candidates
+            .into_iter()
+            .find(|candidate| config.source.join(candidate).is_file())
+    }
+
+    fn localized_url(&self, path: &str, locale: &str) -> String {
+        let config = &self.options.config;
+        let translated_path = self.translated_url(path, locale);
+
+        if locale == config.default_language && config.default_language_at_root {
+            format!("{}/{translated_path}", config.base_url)
+        } else {
+            format!("{}/{locale}/{translated_path}", config.base_url)
+        }
+    }
+
+    fn alternate_urls(&self, path: &str) -> Vec<(String, String)> {
+        let config = &self.options.config;
+        let mut alternates = self
+            .translations
+            .keys()
+            .filter(|locale| **locale != config.default_language)
+            .chain(std::iter::once(&config.default_language))
+            .map(|locale| (locale.clone(), self.localized_url(path, locale)))
+            .collect::<Vec<_>>();
+
+        // Without the default language at the root, the root URL is the
+        // redirect page that picks a language for the visitor.
+        let x_default = if config.default_language_at_root {
+            self.localized_url(path, &config.default_language)
+        } else {
+            format!("{}/{}", config.base_url, filepath_to_output_url(path))
+        };
+        alternates.push(("x-default".to_string(), x_default));
+        alternates
+    }
+
+    fn write_urlset(
+        &self,
+        writer: &mut Writer<Cursor<Vec<u8>>>,
+        entries: &[RoseySitemapEntry],
+    ) -> std::io::Result<()> {
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        writer
+            .create_element("urlset")
+            .with_attributes([
+                ("xmlns", "http://www.sitemaps.org/schemas/sitemap/0.9"),
+                ("xmlns:xhtml", "http://www.w3.org/1999/xhtml"),
+            ])
+            .write_inner_content(|writer| {
+                for entry in entries {
+                    let Some(path) = &entry.path else {
+                        write_url(writer, &entry.loc, &entry.details, &[])?;
+                        continue;
+                    };
+
+                    let alternates = self.alternate_urls(path);
+                    for (_, loc) in alternates
+                        .iter()
+                        .filter(|(locale, _)| locale != "x-default")
+                    {
+                        write_url(writer, loc, &entry.details, &alternates)?;
+                    }
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+fn write_url(
+    writer: &mut Writer<Cursor<Vec<u8>>>,
+    loc: &str,
+    details: &[(String, String)],
+    alternates: &[(String, String)],
+) -> std::io::Result<()> {
+    writer.create_element("url").write_inner_content(|writer| {
+        writer
+            .create_element("loc")
+            .write_text_content(BytesText::new(loc))?;
+        for (name, value) in details {
+            writer
+                .create_element(name.as_str())
+                .write_text_content(BytesText::new(value))?;
+        }
+        for (hreflang, href) in alternates {
+            writer
+                .create_element("xhtml:link")
+                .with_attributes([
+                    ("rel", "alternate"),
+                    ("hreflang", hreflang.as_str()),
+                    ("href", href.as_str()),
+                ])
+                .write_empty()?;
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
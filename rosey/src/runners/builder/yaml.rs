@@ -0,0 +1,277 @@
+use std::{sync::LazyLock, vec::IntoIter};
+
+use regex::Regex;
+use serde_json::Value;
+use yaml_rust2::{parser::Parser, scanner::TScalarStyle, Event, Yaml, YamlLoader};
+
+// The `|` or `>` header of a block scalar, with its indicators and any comment
+static BLOCK_HEADER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[|>][0-9+-]*[ \t]*(#.*)?$").unwrap());
+
+// Writes translated strings back into the original YAML document, so that its
+// comments, quoting, and key order are kept. Documents laid out in a way that
+// can't be edited in place are written as JSON instead, which is also valid YAML.
+pub fn translate_yaml(original: &str, translated: &Value) -> String {
+    YamlEditor::new(original)
+        .and_then(|editor| editor.translate(translated))
+        .unwrap_or_else(|| serde_json::to_string_pretty(translated).unwrap_or_default())
+}
+
+struct YamlEditor {
+    chars: Vec<char>,
+    events: IntoIter<(Event, usize)>,
+    // Character ranges of the original to replace
+    edits: Vec<(usize, usize, String)>,
+}
+
+impl YamlEditor {
+    fn new(original: &str) -> Option<Self> {
+        let mut parser = Parser::new_from_str(original);
+        let mut events = vec![];
+        loop {
+            let (event, marker) = parser.next_token().ok()?;
+            if event == Event::StreamEnd {
+                break;
+            }
+            events.push((event, marker.index()));
+        }
+        Some(YamlEditor {
+            chars: original.chars().collect(),
+            events: events.into_iter(),
+            edits: vec![],
+        })
+    }
+
+    fn translate(mut self, translated: &Value) -> Option<String> {
+        let (event, start) = self
+            .events
+            .find(|(event, _)| !matches!(event, Event::StreamStart | Event::DocumentStart))?;
+        self.node(event, start, Some(translated), false)?;
+
+        let mut output = String::with_capacity(self.chars.len());
+        let mut last = 0;
+        for (start, end, replacement) in self.edits {
+            output.extend(&self.chars[last..start]);
+            output.push_str(&replacement);
+            last = end;
+        }
+        output.extend(&self.chars[last..]);
+        Some(output)
+    }
+
+    fn node(
+        &mut self,
+        event: Event,
+        start: usize,
+        translated: Option<&Value>,
+        flow: bool,
+    ) -> Option<()> {
+        match event {
+            Event::Scalar(value, style, ..) => match translated {
+                Some(Value::String(translated)) if *translated != value => {
+                    let edit = self.scalar_edit(start, &value, style, translated, flow)?;
+                    self.edits.push(edit);
+                }
+                _ => {}
+            },
+            Event::SequenceStart(..) => {
+                let flow = self.chars.get(start) == Some(&'[');
+                let mut index = 0;
+                loop {
+                    let (event, start) = self.events.next()?;
+                    if event == Event::SequenceEnd {
+                        break;
+                    }
+                    let item = translated.and_then(|translated| translated.get(index));
+                    self.node(event, start, item, flow)?;
+                    index += 1;
+                }
+            }
+            Event::MappingStart(..) => {
+                let flow = self.chars.get(start) == Some(&'{');
+                loop {
+                    let (key, key_start) = self.events.next()?;
+                    if key == Event::MappingEnd {
+                        break;
+                    }
+                    let value = match &key {
+                        Event::Scalar(key, ..) => {
+                            translated.and_then(|translated| translated.get(key))
+                        }
+                        _ => None,
+                    };
+                    self.node(key, key_start, None, flow)?;
+                    let (event, start) = self.events.next()?;
+                    self.node(event, start, value, flow)?;
+                }
+            }
+            _ => {}
+        }
+        Some(())
+    }
+
+    fn scalar_edit(
+        &self,
+        start: usize,
+        value: &str,
+        style: TScalarStyle,
+        translated: &str,
+        flow: bool,
+    ) -> Option<(usize, usize, String)> {
+        match style {
+            TScalarStyle::Plain => {
+                let end = self.plain_end(start, value)?;
+                let replacement = if is_plain_safe(translated, flow) {
+                    translated.to_string()
+                } else {
+                    double_quoted(translated)
+                };
+                Some((start, end, replacement))
+            }
+            TScalarStyle::SingleQuoted => {
+                let end = self.quoted_end(start, '\'')?;
+                let replacement = if translated.contains('\n') {
+                    double_quoted(translated)
+                } else {
+                    format!("'{}'", translated.replace('\'', "''"))
+                };
+                Some((start, end, replacement))
+            }
+            TScalarStyle::DoubleQuoted => {
+                let end = self.quoted_end(start, '"')?;
+                Some((start, end, double_quoted(translated)))
+            }
+            TScalarStyle::Literal | TScalarStyle::Folded => {
+                self.block_edit(start, style, translated)
+            }
+        }
+    }
+
+    // Plain scalars are matched against their value, as line breaks within
+    // them are folded into spaces
+    fn plain_end(&self, start: usize, value: &str) -> Option<usize> {
+        let mut end = start;
+        let mut value = value.chars().peekable();
+        while let Some(c) = value.next() {
+            if self.chars.get(end) == Some(&c) {
+                end += 1;
+            } else if matches!(c, ' ' | '\n') && self.is_whitespace(end) {
+                while self.is_whitespace(end) {
+                    end += 1;
+                }
+                while value.next_if_eq(&'\n').is_some() {}
+            } else {
+                return None;
+            }
+        }
+        Some(end)
+    }
+
+    fn quoted_end(&self, start: usize, quote: char) -> Option<usize> {
+        if self.chars.get(start) != Some(&quote) {
+            return None;
+        }
+        let mut end = start + 1;
+        loop {
+            match (*self.chars.get(end)?, self.chars.get(end + 1)) {
+                ('\\', _) if quote == '"' => end += 2,
+                ('\'', Some('\'')) if quote == '\'' => end += 2,
+                (c, _) if c == quote => return Some(end + 1),
+                _ => end += 1,
+            }
+        }
+    }
+
+    // Block scalars are replaced from their header to their last indented line,
+    // keeping the header and indentation they were written with
+    fn block_edit(
+        &self,
+        start: usize,
+        style: TScalarStyle,
+        translated: &str,
+    ) -> Option<(usize, usize, String)> {
+        let line_start = self.line_start(start);
+        let indent = start - line_start;
+        if line_start == 0 || self.chars[line_start..start].iter().any(|c| *c != ' ') {
+            return None;
+        }
+
+        let header_end = line_start - 1;
+        let header_line_start = self.line_start(header_end);
+        let header_line = self.chars[header_line_start..header_end]
+            .iter()
+            .collect::<String>();
+        let header = BLOCK_HEADER.find(&header_line)?;
+        let header_start = header_line_start + header_line[..header.start()].chars().count();
+
+        let mut end = self.line_end(start);
+        let mut next = end + 1;
+        while next < self.chars.len() {
+            let next_end = self.line_end(next);
+            let line = &self.chars[next..next_end];
+            if line.iter().all(|c| c.is_whitespace()) {
+                next = next_end + 1;
+            } else if line.iter().take(indent).all(|c| *c == ' ') && line.len() > indent {
+                end = next_end;
+                next = next_end + 1;
+            } else {
+                break;
+            }
+        }
+
+        let text = translated.trim_end_matches('\n');
+        if text.is_empty() || text.starts_with([' ', '\n']) {
+            return None;
+        }
+        let separator = match style {
+            TScalarStyle::Folded => "\n\n",
+            _ => "\n",
+        };
+        let padding = " ".repeat(indent);
+        let lines = text
+            .split('\n')
+            .map(|line| match line {
+                "" => String::new(),
+                line => format!("{padding}{line}"),
+            })
+            .collect::<Vec<_>>();
+        let replacement = format!("{}\n{}", header.as_str(), lines.join(separator));
+        Some((header_start, end, replacement))
+    }
+
+    fn is_whitespace(&self, index: usize) -> bool {
+        self.chars.get(index).is_some_and(|c| c.is_whitespace())
+    }
+
+    fn line_start(&self, index: usize) -> usize {
+        self.chars[..index]
+            .iter()
+            .rposition(|c| *c == '\n')
+            .map_or(0, |newline| newline + 1)
+    }
+
+    fn line_end(&self, index: usize) -> usize {
+        self.chars[index..]
+            .iter()
+            .position(|c| *c == '\n')
+            .map_or(self.chars.len(), |newline| index + newline)
+    }
+}
+
+// Whether text can be written without quotes and still be read back as the same string
+fn is_plain_safe(text: &str, flow: bool) -> bool {
+    !text.is_empty()
+        && text.trim() == text
+        && !text.contains(['\n', '\t'])
+        && !text.contains(": ")
+        && !text.contains(" #")
+        && !text.ends_with(':')
+        && !(flow && text.contains([',', '[', ']', '{', '}']))
+        && YamlLoader::load_from_str(text)
+            .is_ok_and(|documents| documents == [Yaml::String(text.to_string())])
+}
+
+// JSON strings are also valid double-quoted YAML scalars
+fn double_quoted(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
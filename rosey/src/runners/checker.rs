@@ -1,24 +1,41 @@
+mod report;
+mod sync;
+
 use serde::{Deserialize, Serialize};
-use std::{
-    collections::BTreeMap,
-    fs::{create_dir_all, read_to_string, File},
-    io::{BufWriter, Write},
-    str::FromStr,
-};
+use std::{collections::BTreeMap, fs::read_to_string, str::FromStr};
 
 use globwalk::DirEntry;
 
-use crate::{RoseyLocale, RoseyOptions, RoseyTranslation};
+use super::{parse_locale_file, write_output};
+use crate::{
+    messageformat::placeholders, text_length, RoseyCheckFailure, RoseyError, RoseyLocale,
+    RoseyOptions, RoseyTranslation, RoseyTranslationEntry, SUPPORTED_REVIEW_STATUSES,
+};
 
-#[derive(Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 enum RoseyCheckStates {
     Current,
     Outdated,
+    Mismatched,
+    Overlong,
     Missing,
     Unused,
 }
 
+impl RoseyCheckStates {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RoseyCheckStates::Current => "current",
+            RoseyCheckStates::Outdated => "outdated",
+            RoseyCheckStates::Mismatched => "mismatched",
+            RoseyCheckStates::Overlong => "overlong",
+            RoseyCheckStates::Missing => "missing",
+            RoseyCheckStates::Unused => "unused",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 struct RoseyCheck {
@@ -26,6 +43,8 @@ struct RoseyCheck {
     base_total: i32,
     total: i32,
     states: BTreeMap<RoseyCheckStates, i32>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    statuses: BTreeMap<String, i32>,
     keys: BTreeMap<String, RoseyCheckStates>,
 }
 
@@ -46,50 +65,101 @@ impl From<RoseyOptions> for RoseyChecker {
 }
 
 impl RoseyChecker {
-    pub fn run(&mut self) {
+    pub fn run(&mut self) -> Result<(), RoseyError> {
+        self.read_base_locale()?;
+
         let config = &self.options.config;
         let locale_dest = config.base.clone();
-        let value = read_to_string(&locale_dest).expect("Failed to read locale file");
-        let value = RoseyLocale::from_str(&value);
-        if let Ok(locale) = value {
-            self.base_locale = locale;
-        }
+        let walker =
+            globwalk::GlobWalkerBuilder::from_patterns(&config.locales, &["**/*.{json,po}"])
+                .build()
+                .map_err(|e| RoseyError::io(&config.locales, std::io::Error::other(e)))?
+                .filter_map(Result::ok);
 
-        let walker = globwalk::GlobWalkerBuilder::from_patterns(&config.locales, &["**/*.json"])
-            .build()
-            .unwrap()
-            .filter_map(Result::ok);
+        for entry in walker {
+            self.process_file(entry)?;
+        }
 
-        walker.for_each(|entry| self.process_file(entry));
+        let config = &self.options.config;
+        let (output, extension) = match config.check_format.as_str() {
+            "json" => (serde_json::to_string_pretty(&self.check).unwrap(), "json"),
+            "junit" => (self.junit_report(), "xml"),
+            "markdown" => (self.markdown_report(), "md"),
+            _ => unreachable!("Tried to output an unknown check format."),
+        };
 
         let locale_folder = locale_dest.parent().unwrap();
-        let check_dest = locale_folder.join("checks.json");
-        create_dir_all(locale_folder).unwrap();
-        let output = serde_json::to_string_pretty(&self.check).unwrap();
-
-        if let Ok(file) = File::create(&check_dest) {
-            let mut writer = BufWriter::new(file);
-            if writer.write(output.as_bytes()).is_err() {
-                eprintln!("Failed to write: {check_dest:?}")
-            }
-        } else {
-            eprintln!("Failed to open: {check_dest:?}")
+        let check_dest = locale_folder.join(format!("checks.{extension}"));
+        write_output(&check_dest, &output)?;
+
+        let failures = self.failures();
+        if !failures.is_empty() {
+            return Err(RoseyError::CheckFailed(failures));
         }
+        Ok(())
+    }
+
+    fn read_base_locale(&mut self) -> Result<(), RoseyError> {
+        let base = &self.options.config.base;
+        let value = read_to_string(base).map_err(|e| RoseyError::io(base, e))?;
+        if let Ok(locale) = RoseyLocale::from_str(&value) {
+            self.base_locale = locale;
+        }
+        Ok(())
+    }
+
+    fn failing_states(&self) -> Vec<RoseyCheckStates> {
+        let fail_on = self.options.config.fail_on.as_deref().unwrap_or_default();
+        [
+            RoseyCheckStates::Outdated,
+            RoseyCheckStates::Mismatched,
+            RoseyCheckStates::Overlong,
+            RoseyCheckStates::Missing,
+            RoseyCheckStates::Unused,
+        ]
+        .into_iter()
+        .filter(|state| fail_on.iter().any(|f| f == state.as_str()))
+        .collect()
     }
 
-    fn process_file(&mut self, file: DirEntry) {
+    fn failures(&self) -> Vec<RoseyCheckFailure> {
+        let failing_states = self.failing_states();
+        self.check
+            .iter()
+            .flat_map(|(locale, check)| {
+                failing_states.iter().filter_map(|state| {
+                    let count = check.states.get(state).copied().unwrap_or(0);
+                    (count > 0).then(|| RoseyCheckFailure {
+                        locale: locale.clone(),
+                        state: state.as_str().to_string(),
+                        count,
+                    })
+                })
+            })
+            .collect()
+    }
+
+    fn process_file(&mut self, file: DirEntry) -> Result<(), RoseyError> {
         let locale = file
             .path()
             .file_stem()
             .unwrap()
             .to_string_lossy()
             .to_string();
-        let value = read_to_string(file.path()).expect("Failed to read locale file");
-        let value = serde_json::from_str::<RoseyTranslation>(&value);
-        if let Ok(mut translation) = value {
+        let value = read_to_string(file.path()).map_err(|e| RoseyError::io(file.path(), e))?;
+        if let Ok(mut translation) = parse_locale_file(file.path(), &value, &locale) {
+            if let Some((key, status)) = unknown_status(&translation) {
+                return Err(RoseyError::parse(
+                    file.path(),
+                    format!(
+                        "Unknown review status '{status}' for key '{key}'. Supported review statuses: {SUPPORTED_REVIEW_STATUSES:?}"
+                    ),
+                ));
+            }
             let check = self.check_translation(&mut translation);
             self.check.insert(locale, check);
         }
+        Ok(())
     }
 
     fn check_translation(&mut self, target_keys: &mut RoseyTranslation) -> RoseyCheck {
@@ -102,24 +172,54 @@ impl RoseyChecker {
 
         check.states.insert(RoseyCheckStates::Outdated, 0);
         check.states.insert(RoseyCheckStates::Current, 0);
+        check.states.insert(RoseyCheckStates::Mismatched, 0);
+        check.states.insert(RoseyCheckStates::Overlong, 0);
         check.states.insert(RoseyCheckStates::Missing, 0);
         check.states.insert(RoseyCheckStates::Unused, 0);
 
         let mut target_keys = target_keys.normalize();
+        for status in target_keys
+            .values()
+            .filter_map(|entry| entry.status.clone())
+        {
+            *check.statuses.entry(status).or_insert(0) += 1;
+        }
 
         self.base_locale
             .keys
             .normalize()
             .iter()
             .for_each(|(key, translation)| {
-                if let Some(target_key) = target_keys.get(key) {
-                    if translation.original != target_key.original {
+                if let Some(target_key) = target_keys.get(key).filter(|t| is_translated(t)) {
+                    if translation.original != target_key.original
+                        || target_key.previous_original.is_some()
+                    {
                         check.current = false;
                         let outdated = check.states.entry(RoseyCheckStates::Outdated).or_insert(0);
                         *outdated += 1;
                         check
                             .keys
                             .insert(key.to_string(), RoseyCheckStates::Outdated);
+                    } else if !placeholders_match(
+                        translation.original.as_deref(),
+                        target_key.value.as_deref(),
+                    ) {
+                        check.current = false;
+                        let mismatched = check
+                            .states
+                            .entry(RoseyCheckStates::Mismatched)
+                            .or_insert(0);
+                        *mismatched += 1;
+                        check
+                            .keys
+                            .insert(key.to_string(), RoseyCheckStates::Mismatched);
+                    } else if is_overlong(translation.maxlength, target_key.value.as_deref()) {
+                        check.current = false;
+                        let overlong = check.states.entry(RoseyCheckStates::Overlong).or_insert(0);
+                        *overlong += 1;
+                        check
+                            .keys
+                            .insert(key.to_string(), RoseyCheckStates::Overlong);
                     } else {
                         let current = check.states.entry(RoseyCheckStates::Current).or_insert(0);
                         *current += 1;
@@ -148,3 +248,50 @@ impl RoseyChecker {
         check
     }
 }
+
+fn unknown_status(translation: &RoseyTranslation) -> Option<(&str, &str)> {
+    let RoseyTranslation::V2(keys) = translation else {
+        return None;
+    };
+    keys.iter().find_map(|(key, entry)| {
+        entry
+            .status
+            .as_deref()
+            .filter(|status| !SUPPORTED_REVIEW_STATUSES.contains(status))
+            .map(|status| (key.as_str(), status))
+    })
+}
+
+// Entries added by `rosey sync` have no value until they are translated
+fn is_translated(entry: &RoseyTranslationEntry) -> bool {
+    entry
+        .value
+        .as_deref()
+        .is_some_and(|value| !value.is_empty())
+        || entry
+            .plurals
+            .as_ref()
+            .is_some_and(|plurals| plurals.values().any(|form| !form.is_empty()))
+}
+
+fn is_overlong(max_length: Option<u32>, value: Option<&str>) -> bool {
+    match (max_length, value) {
+        (Some(max_length), Some(value)) => text_length(value) > max_length as usize,
+        _ => false,
+    }
+}
+
+// Translations must use the same message placeholders as the original,
+// otherwise arguments would be silently dropped when building.
+fn placeholders_match(original: Option<&str>, value: Option<&str>) -> bool {
+    let (Some(original), Some(value)) = (original, value) else {
+        return true;
+    };
+    if value.is_empty() {
+        return true;
+    }
+    match placeholders(original) {
+        Some(expected) if !expected.is_empty() => placeholders(value) == Some(expected),
+        _ => true,
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    fmt::Write as FmtWrite,
+    io::{Cursor, Result},
+};
+
+use quick_xml::{
+    events::{BytesDecl, Event},
+    Writer,
+};
+
+use super::{RoseyCheck, RoseyCheckStates, RoseyChecker};
+
+const REPORT_STATES: [RoseyCheckStates; 6] = [
+    RoseyCheckStates::Current,
+    RoseyCheckStates::Outdated,
+    RoseyCheckStates::Mismatched,
+    RoseyCheckStates::Missing,
+    RoseyCheckStates::Unused,
+    RoseyCheckStates::Overlong,
+];
+
+impl RoseyCheck {
+    fn count(&self, state: &RoseyCheckStates) -> i32 {
+        self.states.get(state).copied().unwrap_or(0)
+    }
+}
+
+impl RoseyChecker {
+    pub fn summary_table(&self) -> String {
+        let locale_width = self
+            .check
+            .keys()
+            .map(|locale| locale.chars().count())
+            .chain(std::iter::once("Locale".len()))
+            .max()
+            .unwrap();
+
+        let mut output = String::from("Rosey check summary:\n");
+        write!(output, "  {:<locale_width$}", "Locale").expect("Failed to write summary");
+        for state in REPORT_STATES.iter() {
+            write!(output, "  {:>10}", capitalize(state.as_str()))
+                .expect("Failed to write summary");
+        }
+        output.push('\n');
+
+        for (locale, check) in self.check.iter() {
+            write!(output, "  {locale:<locale_width$}").expect("Failed to write summary");
+            for state in REPORT_STATES.iter() {
+                write!(output, "  {:>10}", check.count(state)).expect("Failed to write summary");
+            }
+            output.push('\n');
+        }
+
+        if self.check.is_empty() {
+            output.push_str("  * no locale files found *\n");
+        }
+
+        if self.check.values().any(|check| !check.statuses.is_empty()) {
+            output.push_str("Review statuses:\n");
+            for (locale, check) in self.check.iter() {
+                let statuses = check
+                    .statuses
+                    .iter()
+                    .map(|(status, count)| format!("{count} {status}"))
+                    .collect::<Vec<_>>();
+                if !statuses.is_empty() {
+                    writeln!(output, "  {locale:<locale_width$}  {}", statuses.join(", "))
+                        .expect("Failed to write summary");
+                }
+            }
+        }
+        output
+    }
+
+    pub fn markdown_report(&self) -> String {
+        let mut output = String::from("## Rosey check\n\n");
+        output.push_str(
+            "| Locale | Current | Outdated | Mismatched | Missing | Unused | Overlong |\n",
+        );
+        output.push_str(
+            "|--------|---------|----------|------------|---------|--------|----------|\n",
+        );
+        for (locale, check) in self.check.iter() {
+            write!(output, "| {locale} |").expect("Failed to write markdown report");
+            for state in REPORT_STATES.iter() {
+                write!(output, " {} |", check.count(state))
+                    .expect("Failed to write markdown report");
+            }
+            output.push('\n');
+        }
+
+        for (locale, check) in self.check.iter() {
+            let keys = check
+                .keys
+                .iter()
+                .filter(|(_, state)| **state != RoseyCheckStates::Current)
+                .collect::<Vec<_>>();
+            if keys.is_empty() {
+                continue;
+            }
+
+            write!(
+                output,
+                "\n### {locale}\n\n| Key | State |\n|-----|-------|\n"
+            )
+            .expect("Failed to write markdown report");
+            for (key, state) in keys {
+                writeln!(
+                    output,
+                    "| `{}` | {} |",
+                    key.replace('|', "\\|"),
+                    state.as_str()
+                )
+                .expect("Failed to write markdown report");
+            }
+        }
+        output
+    }
+
+    pub fn junit_report(&self) -> String {
+        let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+        self.write_junit(&mut writer)
+            .expect("Failed to write JUnit report");
+
+        let mut output = String::from_utf8(writer.into_inner().into_inner()).unwrap();
+        output.push('\n');
+        output
+    }
+
+    fn write_junit(&self, writer: &mut Writer<Cursor<Vec<u8>>>) -> Result<()> {
+        let mut failing_states = self.failing_states();
+        if failing_states.is_empty() {
+            failing_states = vec![
+                RoseyCheckStates::Outdated,
+                RoseyCheckStates::Mismatched,
+                RoseyCheckStates::Overlong,
+                RoseyCheckStates::Missing,
+            ];
+        }
+        let is_failure = |state: &RoseyCheckStates| failing_states.contains(state);
+
+        let tests: usize = self.check.values().map(|check| check.keys.len()).sum();
+        let failures: usize = self
+            .check
+            .values()
+            .map(|check| {
+                check
+                    .keys
+                    .values()
+                    .filter(|state| is_failure(state))
+                    .count()
+            })
+            .sum();
+
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        writer
+            .create_element("testsuites")
+            .with_attributes([
+                ("name", "rosey check"),
+                ("tests", tests.to_string().as_str()),
+                ("failures", failures.to_string().as_str()),
+            ])
+            .write_inner_content(|writer| {
+                for (locale, check) in self.check.iter() {
+                    let failures = check
+                        .keys
+                        .values()
+                        .filter(|state| is_failure(state))
+                        .count();
+                    let skipped = check
+                        .keys
+                        .values()
+                        .filter(|state| **state != RoseyCheckStates::Current && !is_failure(state))
+                        .count();
+
+                    writer
+                        .create_element("testsuite")
+                        .with_attributes([
+                            ("name", locale.as_str()),
+                            ("tests", check.keys.len().to_string().as_str()),
+                            ("failures", failures.to_string().as_str()),
+                            ("skipped", skipped.to_string().as_str()),
+                        ])
+                        .write_inner_content(|writer| {
+                            for (key, state) in check.keys.iter() {
+                                let testcase = writer.create_element("testcase").with_attributes([
+                                    ("classname", format!("rosey.{locale}").as_str()),
+                                    ("name", key.as_str()),
+                                ]);
+
+                                if *state == RoseyCheckStates::Current {
+                                    testcase.write_empty()?;
+                                    continue;
+                                }
+
+                                let element = if is_failure(state) {
+                                    "failure"
+                                } else {
+                                    "skipped"
+                                };
+                                testcase.write_inner_content(|writer| {
+                                    writer
+                                        .create_element(element)
+                                        .with_attribute((
+                                            "message",
+                                            format!("Translation is {}", state.as_str()).as_str(),
+                                        ))
+                                        .write_empty()?;
+                                    Ok(())
+                                })?;
+                            }
+                            Ok(())
+                        })?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
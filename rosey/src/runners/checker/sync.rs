@@ -0,0 +1,90 @@
+use std::{collections::BTreeMap, fs::read_to_string, path::Path};
+
+use super::{RoseyCheckStates, RoseyChecker};
+use crate::{
+    runners::{reject_po_locales, write_output},
+    RoseyError, RoseyTranslation, RoseyTranslationEntry,
+};
+
+impl RoseyChecker {
+    pub fn sync(&mut self) -> Result<(), RoseyError> {
+        self.read_base_locale()?;
+
+        let config = &self.options.config;
+        reject_po_locales(&config.locales, "sync")?;
+        let walker = globwalk::GlobWalkerBuilder::from_patterns(&config.locales, &["**/*.json"])
+            .build()
+            .map_err(|e| RoseyError::io(&config.locales, std::io::Error::other(e)))?
+            .filter_map(Result::ok)
+            .collect::<Vec<_>>();
+
+        for file in walker {
+            let locale = file
+                .path()
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            if !locale.ends_with(".urls") {
+                self.sync_file(file.path(), &locale)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn sync_file(&mut self, file: &Path, locale: &str) -> Result<(), RoseyError> {
+        let content = read_to_string(file).map_err(|e| RoseyError::io(file, e))?;
+        let mut translation = match serde_json::from_str::<RoseyTranslation>(&content) {
+            Ok(translation) if translation.is_empty() => RoseyTranslation::V2(BTreeMap::default()),
+            Ok(translation) => translation,
+            Err(_) => {
+                eprintln!("Failed to parse {file:?}");
+                return Ok(());
+            }
+        };
+
+        let check = self.check_translation(&mut translation);
+        let RoseyTranslation::V2(keys) = &mut translation else {
+            eprintln!("Skipping {file:?}: syncing needs a version 2 locale file");
+            return Ok(());
+        };
+
+        let base_keys = self.base_locale.keys.normalize();
+        let (mut added, mut outdated, mut pruned) = (0, 0, 0);
+        for (key, state) in check.keys {
+            let Some(base_entry) = base_keys.get(&key) else {
+                if state == RoseyCheckStates::Unused && self.options.config.prune_unused {
+                    keys.remove(&key);
+                    pruned += 1;
+                }
+                continue;
+            };
+
+            let entry = keys.entry(key).or_insert_with(|| {
+                added += 1;
+                RoseyTranslationEntry {
+                    original: base_entry.original.clone(),
+                    ..Default::default()
+                }
+            });
+
+            // Outdated translations keep the original they were translated from,
+            // so they are still reported as outdated until reviewed
+            if entry.original != base_entry.original {
+                if entry.previous_original.is_none() {
+                    entry.previous_original = Some(entry.original.take().unwrap_or_default());
+                }
+                entry.original = base_entry.original.clone();
+                outdated += 1;
+            }
+            entry.pages = base_entry.pages.clone();
+            entry.total = base_entry.total;
+            entry.context = base_entry.context.clone();
+            entry.maxlength = base_entry.maxlength;
+        }
+
+        println!("{locale}: {added} added, {outdated} outdated, {pruned} pruned");
+        let output = serde_json::to_string_pretty(&translation).unwrap();
+        write_output(file, &output)
+    }
+}
@@ -0,0 +1,124 @@
+mod xliff;
+
+use std::{fs::read_to_string, str::FromStr};
+
+use super::{parse_locale_file, write_output};
+use crate::{RoseyError, RoseyLocale, RoseyOptions, RoseyTranslation, RoseyTranslationEntry};
+
+pub struct RoseyExportUnit {
+    pub key: String,
+    pub source: String,
+    pub target: Option<String>,
+    pub pages: Vec<String>,
+    pub note: Option<String>,
+    pub outdated: bool,
+}
+
+pub struct RoseyExporter {
+    options: RoseyOptions,
+    base_locale: RoseyLocale,
+}
+
+impl From<RoseyOptions> for RoseyExporter {
+    fn from(options: RoseyOptions) -> Self {
+        RoseyExporter {
+            options,
+            base_locale: RoseyLocale::default(),
+        }
+    }
+}
+
+impl RoseyExporter {
+    pub fn run(&mut self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let value = read_to_string(&config.base).map_err(|e| RoseyError::io(&config.base, e))?;
+        self.base_locale = RoseyLocale::from_str(&value)
+            .map_err(|_| RoseyError::parse(&config.base, "not a Rosey locale file"))?;
+
+        let walker = globwalk::GlobWalkerBuilder::from_patterns(&config.locales, &["*.{json,po}"])
+            .build()
+            .map_err(|e| RoseyError::io(&config.locales, std::io::Error::other(e)))?
+            .filter_map(Result::ok);
+
+        for file in walker {
+            let locale = file
+                .path()
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            if locale.ends_with(".urls") {
+                continue;
+            }
+
+            let value = read_to_string(file.path()).map_err(|e| RoseyError::io(file.path(), e))?;
+            match parse_locale_file(file.path(), &value, &locale) {
+                Ok(translation) => self.export_locale(&locale, &translation)?,
+                Err(_) => eprintln!("Failed to parse {:?}", file.path()),
+            }
+        }
+        Ok(())
+    }
+
+    fn export_locale(
+        &self,
+        locale: &str,
+        translation: &RoseyTranslation,
+    ) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let units = self.collect_units(translation);
+
+        let (output, extension) = match config.export_format.as_str() {
+            "xliff" => (
+                xliff::write(
+                    &units,
+                    &config.default_language,
+                    locale,
+                    &config.xliff_version,
+                ),
+                "xlf",
+            ),
+            _ => unreachable!("Tried to export an unknown format."),
+        };
+
+        let dest = config.exports.join(format!("{locale}.{extension}"));
+        write_output(&dest, &output)
+    }
+
+    fn collect_units(&self, translation: &RoseyTranslation) -> Vec<RoseyExportUnit> {
+        let target_keys = translation.normalize();
+
+        self.base_locale
+            .keys
+            .normalize()
+            .into_iter()
+            .map(|(key, base_entry)| {
+                let RoseyTranslationEntry {
+                    original,
+                    value,
+                    pages,
+                    context,
+                    ..
+                } = base_entry;
+                let source = original.or(value).unwrap_or_default();
+                let target_entry = target_keys.get(&key);
+                let outdated = target_entry.is_some_and(|entry| {
+                    entry.previous_original.is_some()
+                        || entry
+                            .original
+                            .as_ref()
+                            .is_some_and(|original| *original != source)
+                });
+
+                RoseyExportUnit {
+                    target: target_entry.and_then(|entry| entry.value.clone()),
+                    pages: pages.unwrap_or_default().into_keys().collect(),
+                    note: context.and_then(|context| context.note),
+                    key,
+                    source,
+                    outdated,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,179 @@
+use std::io::{Cursor, Result};
+
+use quick_xml::{
+    events::{BytesDecl, BytesText, Event},
+    Writer,
+};
+
+use super::RoseyExportUnit;
+
+pub fn write(
+    units: &[RoseyExportUnit],
+    source_lang: &str,
+    target_lang: &str,
+    version: &str,
+) -> String {
+    let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+    let result = match version {
+        "2.0" => write_v2(&mut writer, units, source_lang, target_lang),
+        _ => write_v1(&mut writer, units, source_lang, target_lang),
+    };
+    result.expect("Failed to write XLIFF");
+
+    let mut output = String::from_utf8(writer.into_inner().into_inner()).unwrap();
+    output.push('\n');
+    output
+}
+
+fn write_v1(
+    writer: &mut Writer<Cursor<Vec<u8>>>,
+    units: &[RoseyExportUnit],
+    source_lang: &str,
+    target_lang: &str,
+) -> Result<()> {
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer
+        .create_element("xliff")
+        .with_attributes([
+            ("version", "1.2"),
+            ("xmlns", "urn:oasis:names:tc:xliff:document:1.2"),
+        ])
+        .write_inner_content(|writer| {
+            writer
+                .create_element("file")
+                .with_attributes([
+                    ("original", "rosey"),
+                    ("datatype", "html"),
+                    ("source-language", source_lang),
+                    ("target-language", target_lang),
+                ])
+                .write_inner_content(|writer| {
+                    writer
+                        .create_element("body")
+                        .write_inner_content(|writer| {
+                            for unit in units {
+                                write_v1_unit(writer, unit)?;
+                            }
+                            Ok(())
+                        })?;
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_v1_unit(writer: &mut Writer<Cursor<Vec<u8>>>, unit: &RoseyExportUnit) -> Result<()> {
+    writer
+        .create_element("trans-unit")
+        .with_attributes([("id", unit.key.as_str()), ("resname", unit.key.as_str())])
+        .write_inner_content(|writer| {
+            writer
+                .create_element("source")
+                .write_text_content(BytesText::new(&unit.source))?;
+            if let Some(target) = &unit.target {
+                let state = if unit.outdated {
+                    "needs-review-translation"
+                } else {
+                    "translated"
+                };
+                writer
+                    .create_element("target")
+                    .with_attribute(("state", state))
+                    .write_text_content(BytesText::new(target))?;
+            }
+            for page in &unit.pages {
+                writer
+                    .create_element("note")
+                    .with_attribute(("from", "rosey"))
+                    .write_text_content(BytesText::new(page))?;
+            }
+            if let Some(note) = &unit.note {
+                writer
+                    .create_element("note")
+                    .with_attribute(("from", "developer"))
+                    .write_text_content(BytesText::new(note))?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_v2(
+    writer: &mut Writer<Cursor<Vec<u8>>>,
+    units: &[RoseyExportUnit],
+    source_lang: &str,
+    target_lang: &str,
+) -> Result<()> {
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer
+        .create_element("xliff")
+        .with_attributes([
+            ("xmlns", "urn:oasis:names:tc:xliff:document:2.0"),
+            ("version", "2.0"),
+            ("srcLang", source_lang),
+            ("trgLang", target_lang),
+        ])
+        .write_inner_content(|writer| {
+            writer
+                .create_element("file")
+                .with_attribute(("id", "rosey"))
+                .write_inner_content(|writer| {
+                    for unit in units {
+                        write_v2_unit(writer, unit)?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_v2_unit(writer: &mut Writer<Cursor<Vec<u8>>>, unit: &RoseyExportUnit) -> Result<()> {
+    writer
+        .create_element("unit")
+        .with_attributes([("id", unit.key.as_str()), ("name", unit.key.as_str())])
+        .write_inner_content(|writer| {
+            if !unit.pages.is_empty() || unit.note.is_some() {
+                writer
+                    .create_element("notes")
+                    .write_inner_content(|writer| {
+                        for page in &unit.pages {
+                            writer
+                                .create_element("note")
+                                .with_attribute(("category", "location"))
+                                .write_text_content(BytesText::new(page))?;
+                        }
+                        if let Some(note) = &unit.note {
+                            writer
+                                .create_element("note")
+                                .with_attribute(("category", "description"))
+                                .write_text_content(BytesText::new(note))?;
+                        }
+                        Ok(())
+                    })?;
+            }
+
+            let state = if unit.target.is_some() && !unit.outdated {
+                "translated"
+            } else {
+                "initial"
+            };
+            writer
+                .create_element("segment")
+                .with_attribute(("state", state))
+                .write_inner_content(|writer| {
+                    writer
+                        .create_element("source")
+                        .write_text_content(BytesText::new(&unit.source))?;
+                    if let Some(target) = &unit.target {
+                        writer
+                            .create_element("target")
+                            .write_text_content(BytesText::new(target))?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    Ok(())
+}
@@ -1,20 +1,19 @@
 mod html;
 mod json;
+mod markdown;
+mod po;
 
-use std::{
-    fs::{create_dir_all, File},
-    io::{BufWriter, Write},
-};
+use std::path::Path;
 
-use globwalk::DirEntry;
-
-use crate::{RoseyLocale, RoseyOptions};
+use super::write_output;
+use crate::{RoseyError, RoseyLocale, RoseyOptions, RoseyWarnings};
 
 pub struct RoseyGenerator {
     pub options: RoseyOptions,
     pub locale: RoseyLocale,
     pub urls_locale: RoseyLocale,
     pub current_file: String,
+    pub(crate) warnings: RoseyWarnings,
 }
 
 impl From<RoseyOptions> for RoseyGenerator {
@@ -25,63 +24,55 @@ impl From<RoseyOptions> for RoseyGenerator {
             locale: RoseyLocale::new(version),
             urls_locale: RoseyLocale::new(version),
             current_file: String::default(),
+            warnings: RoseyWarnings::default(),
         }
     }
 }
 
 impl RoseyGenerator {
-    pub fn run(&mut self) {
+    pub fn run(&mut self) -> Result<(), RoseyError> {
         let config = &self.options.config;
-        let walker =
-            globwalk::GlobWalkerBuilder::from_patterns(&config.source, &["**/*.{htm,html,json}"])
-                .build()
-                .unwrap()
-                .filter_map(Result::ok);
+        let walker = globwalk::GlobWalkerBuilder::from_patterns(
+            &config.source,
+            &["**/*.{htm,html,json,yml,yaml,toml,md,txt}"],
+        )
+        .build()
+        .map_err(|e| RoseyError::io(&config.source, std::io::Error::other(e)))?
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
+
+        for file in walker {
+            self.process_file(file.path())?;
+        }
 
-        walker.for_each(|file| self.process_file(file));
+        self.output_locale()?;
+        self.output_urls()?;
 
-        self.output_locale();
-        self.output_urls();
+        if self.options.config.pot {
+            self.output_pot()?;
+        }
+        Ok(())
     }
 
-    fn output_locale(&mut self) {
+    fn output_locale(&mut self) -> Result<(), RoseyError> {
         let config = &self.options.config;
         let locale_dest = &config.base;
-        let locale_folder = locale_dest.parent().unwrap();
-        create_dir_all(locale_folder).unwrap();
         let output = self.locale.output(config.version);
-
-        if let Ok(file) = File::create(locale_dest) {
-            let mut writer = BufWriter::new(file);
-            if writer.write(output.as_bytes()).is_err() {
-                eprintln!("Failed to write: {locale_dest:?}")
-            }
-        } else {
-            eprintln!("Failed to open: {locale_dest:?}")
-        }
+        write_output(locale_dest, &output)
     }
 
-    fn output_urls(&mut self) {
+    fn output_urls(&mut self) -> Result<(), RoseyError> {
         let config = &self.options.config;
         let urls_dest = &config.base_urls;
-        let urls_folder = urls_dest.parent().unwrap();
-        create_dir_all(urls_folder).unwrap();
         let output = self.urls_locale.output(config.version);
-
-        if let Ok(file) = File::create(urls_dest) {
-            let mut writer = BufWriter::new(file);
-            if writer.write(output.as_bytes()).is_err() {
-                eprintln!("Failed to write: {urls_dest:?}")
-            }
-        } else {
-            eprintln!("Failed to open: {urls_dest:?}")
-        }
+        write_output(urls_dest, &output)
     }
 
-    fn process_file(&mut self, file: DirEntry) {
-        match file.path().extension().map(|ext| ext.to_str().unwrap()) {
-            Some("htm" | "html") => self.process_html_file(file.path()),
-            Some("json") => self.process_json_file(file.path()),
+    fn process_file(&mut self, file: &Path) -> Result<(), RoseyError> {
+        match file.extension().map(|ext| ext.to_str().unwrap()) {
+            Some("htm" | "html") => self.process_html_file(file),
+            Some("json" | "yml" | "yaml" | "toml") => self.process_json_file(file),
+            Some("md" | "txt") => self.process_markdown_file(file),
             _ => unreachable!("Tried to process unknown file type."),
         }
     }
@@ -5,11 +5,17 @@ use sha2::{Digest, Sha256};
 use std::{collections::BTreeMap, fmt::Write, fs::read_to_string, path::Path};
 
 use super::RoseyGenerator;
+use crate::{
+    attribute_max_length, element_max_length, element_message_args,
+    messageformat::message_template, RoseyError, RoseyTranslation, RoseyTranslationContext,
+    RoseyWarnings,
+};
 
 impl RoseyGenerator {
-    pub fn process_html_file(&mut self, file: &Path) {
+    pub fn process_html_file(&mut self, file: &Path) -> Result<(), RoseyError> {
         let config = &self.options.config;
-        let dom = kuchiki::parse_html().one(read_to_string(file).unwrap());
+        let content = read_to_string(file).map_err(|e| RoseyError::io(file, e))?;
+        let dom = kuchiki::parse_html().one(content);
         crate::inline_templates(&dom);
         self.current_file = file
             .strip_prefix(&config.source)
@@ -19,6 +25,7 @@ impl RoseyGenerator {
         self.urls_locale
             .insert_uncounted(self.current_file.clone(), self.current_file.clone());
         self.process_html_node(dom, None, None);
+        Ok(())
     }
 
     fn process_html_node(
@@ -62,6 +69,17 @@ impl RoseyGenerator {
                     .expect("Failed to write namespace to prefix");
             }
 
+            let has_keys = attributes.contains(&config.tag[..])
+                || attributes.contains(format!("{}-attrs-explicit", config.tag));
+            let context = (config.context && has_keys)
+                .then(|| element_context(&node, attributes.get(format!("{}-note", config.tag))));
+            let max_length = attributes
+                .contains(&config.tag[..])
+                .then(|| element_max_length(element, &config.tag, &config.max_lengths))
+                .and_then(|max_length| {
+                    valid_attribute(&self.warnings, &self.current_file, max_length)
+                });
+
             if let Some(key) = attributes.get(&config.tag[..]) {
                 let key = if key.is_empty() {
                     let mut hasher = Sha256::new();
@@ -75,27 +93,69 @@ impl RoseyGenerator {
                 if let Some(attrs) = attributes.get(format!("{}-attrs", config.tag)) {
                     for attr in attrs.split(',') {
                         if let Some(value) = attributes.get(attr) {
+                            let attr_key = format!("{}.{}", key, attr);
+                            let max_length = valid_attribute(
+                                &self.warnings,
+                                &self.current_file,
+                                attribute_max_length(element, &config.tag, attr),
+                            );
                             self.locale.insert(
-                                format!("{}.{}", key, attr),
+                                attr_key.clone(),
                                 String::from(value),
                                 &self.current_file,
                             );
+                            insert_key_metadata(
+                                &mut self.locale.keys,
+                                &attr_key,
+                                context.as_ref(),
+                                max_length,
+                            );
                         }
                     }
                 }
 
-                let inner_html: String = node.children().map(|child| child.to_string()).collect();
-                self.locale.insert(key, inner_html, &self.current_file);
+                let mut inner_html: String =
+                    node.children().map(|child| child.to_string()).collect();
+                if let Some(args) = valid_attribute(
+                    &self.warnings,
+                    &self.current_file,
+                    element_message_args(element, &config.tag, &key),
+                ) {
+                    inner_html = message_template(&inner_html, &args);
+                }
+                self.locale
+                    .insert(key.clone(), inner_html.clone(), &self.current_file);
+                insert_key_metadata(&mut self.locale.keys, &key, context.as_ref(), max_length);
+
+                if let Some(count) = attributes.get(format!("{}-count", config.tag)) {
+                    if config.version >= 3 {
+                        self.locale.keys.insert_plurals(
+                            &key,
+                            &inner_html,
+                            &config.default_language,
+                            count,
+                        );
+                    }
+                }
             }
 
             if let Some(attrs_map) = attributes.get(format!("{}-attrs-explicit", config.tag)) {
                 let attrs_map: BTreeMap<String, String> = serde_json::from_str(attrs_map).expect("Failed to parse explicit attrs. Must be a JSON object with string keys and values.");
                 for (attr, key) in attrs_map.iter() {
                     if let Some(value) = attributes.get(attr.as_str()) {
-                        self.locale.insert(
-                            format!("{}{}", prefix, key),
-                            String::from(value),
+                        let key = format!("{}{}", prefix, key);
+                        let max_length = valid_attribute(
+                            &self.warnings,
                             &self.current_file,
+                            attribute_max_length(element, &config.tag, attr),
+                        );
+                        self.locale
+                            .insert(key.clone(), String::from(value), &self.current_file);
+                        insert_key_metadata(
+                            &mut self.locale.keys,
+                            &key,
+                            context.as_ref(),
+                            max_length,
                         );
                     }
                 }
@@ -107,3 +167,101 @@ impl RoseyGenerator {
         }
     }
 }
+
+fn valid_attribute<T>(
+    warnings: &RoseyWarnings,
+    file: &str,
+    value: Result<Option<T>, String>,
+) -> Option<T> {
+    value.unwrap_or_else(|warning| {
+        warnings.warn(file, warning);
+        None
+    })
+}
+
+fn insert_key_metadata(
+    keys: &mut RoseyTranslation,
+    key: &str,
+    context: Option<&RoseyTranslationContext>,
+    max_length: Option<u32>,
+) {
+    if let Some(context) = context {
+        keys.insert_context(key, context);
+    }
+    if let Some(max_length) = max_length {
+        keys.insert_max_length(key, max_length);
+    }
+}
+
+fn element_context(node: &NodeRef, note: Option<&str>) -> RoseyTranslationContext {
+    RoseyTranslationContext {
+        element: node.as_element().unwrap().name.local.to_string(),
+        selector: css_path(node),
+        before: node
+            .preceding_siblings()
+            .find_map(|sibling| text_snippet(&sibling, true)),
+        after: node
+            .following_siblings()
+            .find_map(|sibling| text_snippet(&sibling, false)),
+        note: note.map(String::from),
+    }
+}
+
+// A selector from the nearest ancestor with an id, or from the document root
+fn css_path(node: &NodeRef) -> String {
+    let mut parts = vec![];
+    for ancestor in node.inclusive_ancestors() {
+        let Some(element) = ancestor.as_element() else {
+            continue;
+        };
+        let name = element.name.local.to_string();
+        if let Some(id) = element.attributes.borrow().get("id") {
+            parts.push(format!("{name}#{id}"));
+            break;
+        }
+
+        let same_tag = |sibling: &NodeRef| {
+            sibling
+                .as_element()
+                .is_some_and(|e| e.name.local == element.name.local)
+        };
+        let index = ancestor.preceding_siblings().filter(same_tag).count();
+        let others = index + ancestor.following_siblings().filter(same_tag).count();
+        if others > 0 {
+            parts.push(format!("{name}:nth-of-type({})", index + 1));
+        } else {
+            parts.push(name);
+        }
+    }
+    parts.reverse();
+    parts.join(" > ")
+}
+
+// The text of a neighbouring node, cut down to the end nearest the element
+fn text_snippet(node: &NodeRef, before: bool) -> Option<String> {
+    const SNIPPET_LENGTH: usize = 80;
+
+    if node
+        .as_element()
+        .is_some_and(|e| matches!(&*e.name.local, "script" | "style" | "template"))
+    {
+        return None;
+    }
+    let words = node.text_contents();
+    let words = words.split_whitespace().collect::<Vec<_>>();
+    if words.is_empty() {
+        return None;
+    }
+
+    let text = words.join(" ");
+    let length = text.chars().count();
+    if length <= SNIPPET_LENGTH {
+        return Some(text);
+    }
+    Some(if before {
+        let skip = length - SNIPPET_LENGTH;
+        format!("…{}", text.chars().skip(skip).collect::<String>())
+    } else {
+        format!("{}…", text.chars().take(SNIPPET_LENGTH).collect::<String>())
+    })
+}
@@ -8,37 +8,51 @@ use std::{
 
 use serde_json::Value;
 
+use crate::{
+    runners::{array_item_namespace, data_file_extension, parse_data_file, schema_rule_matches},
+    RoseyError,
+};
+
 impl RoseyGenerator {
-    pub fn process_json_file(&mut self, file: &Path) {
+    pub fn process_json_file(&mut self, file: &Path) -> Result<(), RoseyError> {
         let config = &self.options.config;
+        let Some(extension) = data_file_extension(file) else {
+            return Ok(());
+        };
         let mut schema_path = PathBuf::from(file);
-        schema_path.set_extension("rosey.json");
+        schema_path.set_extension(format!("rosey.{extension}"));
         if !schema_path.exists() {
-            return;
+            return Ok(());
         }
 
-        let source = serde_json::from_str::<Value>(&read_to_string(file).unwrap());
-        let schema = serde_json::from_str::<Value>(&read_to_string(&schema_path).unwrap());
-
-        if source.is_err() {
-            eprintln!("Failed to parse {file:?}");
-            return;
-        }
-
-        if schema.is_err() {
-            eprintln!("Failed to parse {schema_path:?}");
-            return;
-        }
+        let content = read_to_string(file).map_err(|e| RoseyError::io(file, e))?;
+        let schema_content =
+            read_to_string(&schema_path).map_err(|e| RoseyError::io(&schema_path, e))?;
+        let source = parse_data_file(file, &content)?;
+        let schema = parse_data_file(&schema_path, &schema_content)?;
 
         self.current_file =
             String::from(file.strip_prefix(&config.source).unwrap().to_str().unwrap());
 
-        self.process_json_node(&source.unwrap(), &schema.unwrap(), None)
+        if let Some(matches) =
+            schema_rule_matches(&source, &schema, &self.current_file, &self.warnings)
+        {
+            for matched in matches {
+                if let Some(Value::String(value)) = source.pointer(&matched.pointer) {
+                    self.locale
+                        .insert(matched.key, value.clone(), &self.current_file);
+                }
+            }
+            return Ok(());
+        }
+
+        self.process_json_node(&source, &schema, None);
+        Ok(())
     }
 
     fn process_json_node(&mut self, source: &Value, schema: &Value, namespace: Option<String>) {
         if discriminant(source) != discriminant(schema) {
-            eprintln!("Schema mismatch");
+            self.warnings.warn(&self.current_file, "Schema mismatch");
             return;
         }
 
@@ -78,6 +92,31 @@ impl RoseyGenerator {
                 let mut key = None;
 
                 match schema_array.first() {
+                    Some(Value::String(schema_value))
+                        if schema_value.trim().starts_with("rosey-array-ns:") =>
+                    {
+                        let field = schema_value.trim().strip_prefix("rosey-array-ns:").unwrap();
+                        let Some(item_schema) = schema_array.get(1) else {
+                            let message = format!(
+                                "Schema mismatch in array: Expected a schema after {schema_value}"
+                            );
+                            self.warnings.warn(&self.current_file, message);
+                            return;
+                        };
+
+                        source_array
+                            .iter()
+                            .enumerate()
+                            .for_each(|(index, source_value)| {
+                                let item_namespace =
+                                    array_item_namespace(source_value, field, index);
+                                self.process_json_node(
+                                    source_value,
+                                    item_schema,
+                                    Some(format!("{namespace}{item_namespace}.")),
+                                )
+                            })
+                    }
                     Some(Value::String(schema_value)) => {
                         schema_value.trim().split('|').for_each(|part| {
                             if part == "rosey-array-ns" {
@@ -101,14 +140,20 @@ impl RoseyGenerator {
                                     &self.current_file,
                                 );
                             } else {
-                                eprintln!("Schema mismatch in array: Expected String")
+                                self.warnings.warn(
+                                    &self.current_file,
+                                    "Schema mismatch in array: Expected String",
+                                )
                             }
                         })
                     }
                     Some(schema_value) => source_array.iter().for_each(|source_value| {
                         self.process_json_node(source_value, schema_value, None)
                     }),
-                    _ => eprintln!("Schema mismatch in array: Expected String|Object"),
+                    _ => self.warnings.warn(
+                        &self.current_file,
+                        "Schema mismatch in array: Expected String|Object",
+                    ),
                 }
             }
             _ => (),
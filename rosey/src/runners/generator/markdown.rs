@@ -0,0 +1,24 @@
+use super::RoseyGenerator;
+
+use std::{fs::read, path::Path};
+
+use crate::{markdown::markdown_keys, RoseyError};
+
+impl RoseyGenerator {
+    pub fn process_markdown_file(&mut self, file: &Path) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let bytes = read(file).map_err(|e| RoseyError::io(file, e))?;
+        // Files that aren't UTF-8 text can't contain markers
+        let Ok(content) = String::from_utf8(bytes) else {
+            return Ok(());
+        };
+
+        self.current_file =
+            String::from(file.strip_prefix(&config.source).unwrap().to_str().unwrap());
+
+        for (key, value) in markdown_keys(&content) {
+            self.locale.insert(key, value, &self.current_file);
+        }
+        Ok(())
+    }
+}
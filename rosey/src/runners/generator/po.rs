@@ -0,0 +1,60 @@
+use std::fmt::Write as FmtWrite;
+
+use super::RoseyGenerator;
+use crate::{runners::write_output, RoseyError};
+
+impl RoseyGenerator {
+    pub fn output_pot(&mut self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let pot_dest = config.base.with_extension("pot");
+
+        let mut output = String::from(
+            "msgid \"\"\n\
+             msgstr \"\"\n\
+             \"Content-Type: text/plain; charset=UTF-8\\n\"\n\
+             \"Content-Transfer-Encoding: 8bit\\n\"\n\
+             \"X-Generator: Rosey\\n\"\n",
+        );
+
+        for (key, entry) in self.locale.keys.normalize() {
+            let original = entry.original.or(entry.value).unwrap_or_default();
+
+            output.push('\n');
+            // One reference per line, with paths containing spaces isolated
+            // the way GNU gettext writes them
+            for page in entry.pages.unwrap_or_default().into_keys() {
+                let page = if page.contains(char::is_whitespace) {
+                    format!("\u{2068}{page}\u{2069}")
+                } else {
+                    page
+                };
+                writeln!(output, "#: {page}").expect("Failed to write POT references");
+            }
+            writeln!(output, "msgctxt {}", po_string(&key)).expect("Failed to write POT context");
+            writeln!(output, "msgid {}", po_string(&original)).expect("Failed to write POT msgid");
+            writeln!(output, "msgstr \"\"").expect("Failed to write POT msgstr");
+        }
+
+        write_output(&pot_dest, &output)
+    }
+}
+
+fn po_string(s: &str) -> String {
+    let escape = |line: &str| {
+        line.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\t', "\\t")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n")
+    };
+
+    if !s.trim_end_matches('\n').contains('\n') {
+        return format!("\"{}\"", escape(s));
+    }
+
+    let mut output = String::from("\"\"");
+    for line in s.split_inclusive('\n') {
+        write!(output, "\n\"{}\"", escape(line)).expect("Failed to write POT string");
+    }
+    output
+}
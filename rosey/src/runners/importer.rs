@@ -0,0 +1,124 @@
+mod xliff;
+
+use std::{collections::BTreeMap, fs::read_to_string, path::Path, str::FromStr};
+
+use super::write_output;
+use crate::{RoseyError, RoseyLocale, RoseyOptions, RoseyTranslation, RoseyTranslationEntry};
+
+#[derive(Default)]
+pub struct RoseyImportUnit {
+    pub key: String,
+    pub source: Option<String>,
+    pub target: Option<String>,
+}
+
+#[derive(Default)]
+pub struct RoseyImportFile {
+    pub target_language: Option<String>,
+    pub units: Vec<RoseyImportUnit>,
+}
+
+pub struct RoseyImporter {
+    options: RoseyOptions,
+    base_locale: RoseyLocale,
+}
+
+impl From<RoseyOptions> for RoseyImporter {
+    fn from(options: RoseyOptions) -> Self {
+        RoseyImporter {
+            options,
+            base_locale: RoseyLocale::default(),
+        }
+    }
+}
+
+impl RoseyImporter {
+    pub fn run(&mut self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        if let Ok(value) = read_to_string(&config.base) {
+            if let Ok(locale) = RoseyLocale::from_str(&value) {
+                self.base_locale = locale;
+            }
+        }
+
+        let walker =
+            globwalk::GlobWalkerBuilder::from_patterns(&config.exports, &["*.{xlf,xliff}"])
+                .build()
+                .unwrap()
+                .filter_map(Result::ok);
+
+        for file in walker {
+            self.process_file(file.path())?;
+        }
+        Ok(())
+    }
+
+    fn process_file(&self, file: &Path) -> Result<(), RoseyError> {
+        let content = read_to_string(file).map_err(|e| RoseyError::io(file, e))?;
+        let imported = match xliff::read(&content) {
+            Ok(imported) => imported,
+            Err(e) => {
+                eprintln!("Failed to parse {file:?}: {e}");
+                return Ok(());
+            }
+        };
+
+        let locale = imported
+            .target_language
+            .clone()
+            .unwrap_or_else(|| file.file_stem().unwrap().to_string_lossy().to_string());
+
+        self.import_locale(&locale, imported)
+    }
+
+    fn import_locale(&self, locale: &str, imported: RoseyImportFile) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let locale_path = config.locales.join(format!("{locale}.json"));
+
+        let mut translation = read_to_string(&locale_path)
+            .ok()
+            .and_then(|value| serde_json::from_str::<RoseyTranslation>(&value).ok())
+            .filter(|translation| !translation.is_empty())
+            .unwrap_or_else(|| match config.version {
+                1 => RoseyTranslation::V1(BTreeMap::default()),
+                _ => RoseyTranslation::V2(BTreeMap::default()),
+            });
+
+        let base_keys = self.base_locale.keys.normalize();
+
+        for RoseyImportUnit {
+            key,
+            source,
+            target,
+        } in imported.units
+        {
+            let Some(target) = target else {
+                continue;
+            };
+
+            match &mut translation {
+                RoseyTranslation::V1(keys) => {
+                    keys.insert(key, target);
+                }
+                RoseyTranslation::V2(keys) => {
+                    let entry = keys.entry(key).or_insert_with_key(|key| {
+                        let base_entry = base_keys.get(key);
+                        RoseyTranslationEntry {
+                            pages: base_entry.and_then(|entry| entry.pages.clone()),
+                            total: base_entry.and_then(|entry| entry.total),
+                            ..Default::default()
+                        }
+                    });
+                    if source.is_some() {
+                        entry.original = source;
+                        entry.previous_original = None;
+                    }
+                    entry.value = Some(target);
+                }
+            }
+        }
+
+        let output = serde_json::to_string_pretty(&translation).unwrap();
+        write_output(&locale_path, &output)
+    }
+}
@@ -0,0 +1,103 @@
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use super::{RoseyImportFile, RoseyImportUnit};
+
+enum Capture {
+    Source,
+    Target,
+}
+
+pub fn read(content: &str) -> Result<RoseyImportFile, quick_xml::Error> {
+    let mut reader = Reader::from_str(content);
+    let mut file = RoseyImportFile::default();
+    let mut unit: Option<RoseyImportUnit> = None;
+    let mut capture: Option<Capture> = None;
+    let mut depth = 0;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                if capture.is_some() {
+                    depth += 1;
+                    continue;
+                }
+                match e.local_name().as_ref() {
+                    b"xliff" | b"file" => read_languages(&e, &mut file),
+                    b"trans-unit" | b"unit" => {
+                        unit = attribute(&e, "id").map(|key| RoseyImportUnit {
+                            key,
+                            ..Default::default()
+                        })
+                    }
+                    b"source" if unit.is_some() => capture = Some(Capture::Source),
+                    b"target" if unit.is_some() => capture = Some(Capture::Target),
+                    _ => {}
+                }
+            }
+            Event::Empty(e) if capture.is_none() => match e.local_name().as_ref() {
+                b"xliff" | b"file" => read_languages(&e, &mut file),
+                b"target" => {
+                    if let Some(unit) = unit.as_mut() {
+                        unit.target.get_or_insert_with(String::new);
+                    }
+                }
+                _ => {}
+            },
+            Event::End(e) => {
+                if capture.is_some() {
+                    if depth > 0 {
+                        depth -= 1;
+                    } else {
+                        capture = None;
+                    }
+                    continue;
+                }
+                if matches!(e.local_name().as_ref(), b"trans-unit" | b"unit") {
+                    if let Some(unit) = unit.take() {
+                        file.units.push(unit);
+                    }
+                }
+            }
+            Event::Text(e) => {
+                if let (Some(capture), Some(unit)) = (&capture, unit.as_mut()) {
+                    let text = e.unescape()?;
+                    push_text(capture, unit, &text);
+                }
+            }
+            Event::CData(e) => {
+                if let (Some(capture), Some(unit)) = (&capture, unit.as_mut()) {
+                    let text = String::from_utf8_lossy(&e);
+                    push_text(capture, unit, &text);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(file)
+}
+
+fn push_text(capture: &Capture, unit: &mut RoseyImportUnit, text: &str) {
+    let value = match capture {
+        Capture::Source => unit.source.get_or_insert_with(String::new),
+        Capture::Target => unit.target.get_or_insert_with(String::new),
+    };
+    value.push_str(text);
+}
+
+fn read_languages(e: &BytesStart, file: &mut RoseyImportFile) {
+    if let Some(lang) = attribute(e, "trgLang").or_else(|| attribute(e, "target-language")) {
+        file.target_language = Some(lang);
+    }
+}
+
+fn attribute(e: &BytesStart, name: &str) -> Option<String> {
+    e.try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|attr| attr.unescape_value().ok().map(|value| value.into_owned()))
+}
@@ -1,3 +1,144 @@
 pub mod builder;
 pub mod checker;
+pub mod exporter;
 pub mod generator;
+pub mod importer;
+mod po;
+pub mod suggester;
+pub mod translator;
+
+use std::{
+    fs::{create_dir_all, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use serde_json::{Map, Number, Value};
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::{
+    jsonpath::{rule_matches, schema_rules, RoseyRuleMatch},
+    RoseyError, RoseyTranslation, RoseyWarnings,
+};
+
+pub fn write_output(dest: &Path, output: &str) -> Result<(), RoseyError> {
+    if let Some(folder) = dest.parent() {
+        create_dir_all(folder).map_err(|e| RoseyError::io(folder, e))?;
+    }
+
+    let file = File::create(dest).map_err(|e| RoseyError::io(dest, e))?;
+    let mut writer = BufWriter::new(file);
+    writer
+        .write_all(output.as_bytes())
+        .map_err(|e| RoseyError::io(dest, e))
+}
+
+pub fn data_file_extension(path: &Path) -> Option<&str> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext @ ("json" | "yml" | "yaml" | "toml")) => Some(ext),
+        _ => None,
+    }
+}
+
+// JSON, YAML and TOML data files are all translated as JSON values
+// Locale files are either Rosey JSON, or gettext `.po` files
+pub fn parse_locale_file(
+    path: &Path,
+    content: &str,
+    locale: &str,
+) -> Result<RoseyTranslation, serde_json::Error> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("po") => Ok(po::read_po_translation(content, locale)),
+        _ => serde_json::from_str(content),
+    }
+}
+
+// Commands that write to locale files only support Rosey JSON
+pub fn reject_po_locales(locales: &Path, command: &str) -> Result<(), RoseyError> {
+    let po_file = globwalk::GlobWalkerBuilder::from_patterns(locales, &["**/*.po"])
+        .build()
+        .map_err(|e| RoseyError::io(locales, std::io::Error::other(e)))?
+        .filter_map(Result::ok)
+        .next();
+    match po_file {
+        Some(file) => Err(RoseyError::InvalidConfig(format!(
+            "rosey {command} can't update gettext locale files such as {:?}. Update .po files with your translation tools instead, or use Rosey JSON locale files",
+            file.path()
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub fn parse_data_file(path: &Path, content: &str) -> Result<Value, RoseyError> {
+    match data_file_extension(path) {
+        Some("yml" | "yaml") => YamlLoader::load_from_str(content)
+            .map(|documents| documents.first().map(yaml_to_value).unwrap_or_default())
+            .map_err(|e| e.to_string()),
+        Some("toml") => toml::from_str(content).map_err(|e| e.to_string()),
+        _ => serde_json::from_str(content).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| RoseyError::parse(path, e))
+}
+
+fn yaml_to_value(yaml: &Yaml) -> Value {
+    match yaml {
+        Yaml::String(value) => Value::String(value.clone()),
+        Yaml::Integer(value) => Value::from(*value),
+        Yaml::Real(value) => value
+            .parse()
+            .ok()
+            .and_then(Number::from_f64)
+            .map_or_else(|| Value::String(value.clone()), Value::Number),
+        Yaml::Boolean(value) => Value::Bool(*value),
+        Yaml::Array(items) => Value::Array(items.iter().map(yaml_to_value).collect()),
+        Yaml::Hash(entries) => Value::Object(
+            entries
+                .iter()
+                .filter_map(|(key, value)| {
+                    let key = match key {
+                        Yaml::String(key) | Yaml::Real(key) => key.clone(),
+                        Yaml::Integer(key) => key.to_string(),
+                        Yaml::Boolean(key) => key.to_string(),
+                        _ => return None,
+                    };
+                    Some((key, yaml_to_value(value)))
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+// The namespace for an item in an array tagged with `rosey-array-ns:<field>`,
+// taken from that field of the item or from its position with `$index`.
+// Items missing the field use `$<index>`, so they can't collide with field values.
+pub fn array_item_namespace(item: &Value, field: &str, index: usize) -> String {
+    if field == "$index" {
+        return index.to_string();
+    }
+    match item.get(field) {
+        Some(Value::String(value)) => value.to_lowercase(),
+        Some(Value::Number(value)) => value.to_string(),
+        _ => format!("${index}"),
+    }
+}
+
+// Matches a schema made of JSONPath rules against the source, reporting any rules
+// that don't apply. Returns None for schemas that mirror the source instead.
+pub fn schema_rule_matches(
+    source: &Value,
+    schema: &Value,
+    file: &str,
+    warnings: &RoseyWarnings,
+) -> Option<Vec<RoseyRuleMatch>> {
+    let rules = schema_rules(schema)?;
+    let mut matches = vec![];
+    for (path, directive) in rules {
+        let (rule_matches, errors) = rule_matches(source, path, directive);
+        for error in errors {
+            warnings.warn(file, format!("Schema rule {path:?} {error}"));
+        }
+        matches.extend(rule_matches);
+    }
+    Some(matches)
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use crate::{plurals::plural_categories, RoseyTranslation, RoseyTranslationEntry};
+
+// gettext numbers plural forms from the fewest items up, so `msgstr[n]` is
+// read as the nth CLDR category of the locale in this order
+const PLURAL_ORDER: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+#[derive(Default)]
+struct PoEntry {
+    msgctxt: Option<String>,
+    msgid: Option<String>,
+    msgstr: Option<String>,
+    plural_forms: Vec<String>,
+    fuzzy: bool,
+}
+
+enum PoField {
+    Msgctxt,
+    Msgid,
+    Msgstr,
+    PluralForm(usize),
+    Ignored,
+}
+
+pub fn read_po_translation(content: &str, locale: &str) -> RoseyTranslation {
+    let mut categories = plural_categories(locale);
+    categories.sort_by_key(|category| PLURAL_ORDER.iter().position(|c| c == category));
+
+    let mut keys = BTreeMap::default();
+    let mut entry = PoEntry::default();
+    let mut field = PoField::Ignored;
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('#') {
+            if entry.msgid.is_some() {
+                finish_entry(&mut keys, std::mem::take(&mut entry), &categories);
+            }
+            if let Some(flags) = line.strip_prefix("#,") {
+                entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            }
+            field = PoField::Ignored;
+            continue;
+        }
+
+        let (keyword, value) = match line.split_once(char::is_whitespace) {
+            Some((keyword, value)) if !line.starts_with('"') => (keyword, value.trim()),
+            _ => ("", line),
+        };
+
+        match keyword {
+            "msgctxt" => {
+                if entry.msgid.is_some() {
+                    finish_entry(&mut keys, std::mem::take(&mut entry), &categories);
+                }
+                entry.msgctxt = Some(unquote(value));
+                field = PoField::Msgctxt;
+            }
+            "msgid" => {
+                if entry.msgid.is_some() {
+                    finish_entry(&mut keys, std::mem::take(&mut entry), &categories);
+                }
+                entry.msgid = Some(unquote(value));
+                field = PoField::Msgid;
+            }
+            "msgstr" => {
+                entry.msgstr = Some(unquote(value));
+                field = PoField::Msgstr;
+            }
+            keyword if keyword.starts_with("msgstr[") => {
+                let index = keyword
+                    .trim_start_matches("msgstr[")
+                    .trim_end_matches(']')
+                    .parse::<usize>();
+                field = match index {
+                    Ok(index) => {
+                        if entry.plural_forms.len() <= index {
+                            entry.plural_forms.resize(index + 1, String::new());
+                        }
+                        entry.plural_forms[index] = unquote(value);
+                        PoField::PluralForm(index)
+                    }
+                    Err(_) => PoField::Ignored,
+                };
+            }
+            "" => {
+                let target = match field {
+                    PoField::Msgctxt => entry.msgctxt.as_mut(),
+                    PoField::Msgid => entry.msgid.as_mut(),
+                    PoField::Msgstr => entry.msgstr.as_mut(),
+                    PoField::PluralForm(index) => entry.plural_forms.get_mut(index),
+                    PoField::Ignored => None,
+                };
+                if let Some(target) = target {
+                    target.push_str(&unquote(value));
+                }
+            }
+            _ => field = PoField::Ignored,
+        }
+    }
+    finish_entry(&mut keys, entry, &categories);
+
+    RoseyTranslation::V2(keys)
+}
+
+fn finish_entry(
+    keys: &mut BTreeMap<String, RoseyTranslationEntry>,
+    entry: PoEntry,
+    categories: &[&str],
+) {
+    let PoEntry {
+        msgctxt,
+        msgid: Some(msgid),
+        msgstr,
+        plural_forms,
+        fuzzy: false,
+    } = entry
+    else {
+        return;
+    };
+
+    // Plural entries use their last form, `other`, as the value
+    let value = msgstr.or_else(|| plural_forms.last().cloned());
+    let Some(value) = value.filter(|value| !value.is_empty()) else {
+        return;
+    };
+    if msgctxt.is_none() && msgid.is_empty() {
+        return;
+    }
+
+    let plurals = (!plural_forms.is_empty()).then(|| {
+        categories
+            .iter()
+            .zip(plural_forms)
+            .filter(|(_, form)| !form.is_empty())
+            .map(|(category, form)| (category.to_string(), form))
+            .collect()
+    });
+
+    keys.insert(
+        msgctxt.unwrap_or_else(|| msgid.clone()),
+        RoseyTranslationEntry {
+            original: Some(msgid),
+            value: Some(value),
+            plurals,
+            ..Default::default()
+        },
+    );
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.strip_prefix('"').unwrap_or(value);
+    let value = value.strip_suffix('"').unwrap_or(value);
+
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('t') => output.push('\t'),
+            Some('r') => output.push('\r'),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+    output
+}
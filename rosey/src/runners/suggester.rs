@@ -0,0 +1,230 @@
+use std::{collections::BTreeMap, fs::read_to_string, path::Path, str::FromStr};
+
+use super::{reject_po_locales, write_output};
+use crate::{RoseyError, RoseyLocale, RoseyOptions, RoseyTranslation, RoseyTranslationEntry};
+
+// Past original → value pairs for each locale
+type RoseyMemory = BTreeMap<String, BTreeMap<String, String>>;
+
+// Remembered originals grouped by their length in characters
+type RoseyMemoryLengths<'a> = BTreeMap<usize, Vec<(&'a String, &'a String, Vec<char>)>>;
+
+pub struct RoseySuggester {
+    options: RoseyOptions,
+    base_locale: RoseyLocale,
+    memory: RoseyMemory,
+}
+
+struct RoseySuggestion<'a> {
+    original: &'a str,
+    value: &'a str,
+    exact: bool,
+}
+
+impl From<RoseyOptions> for RoseySuggester {
+    fn from(options: RoseyOptions) -> Self {
+        RoseySuggester {
+            options,
+            base_locale: RoseyLocale::default(),
+            memory: RoseyMemory::default(),
+        }
+    }
+}
+
+impl RoseySuggester {
+    pub fn run(&mut self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let base = read_to_string(&config.base).map_err(|e| RoseyError::io(&config.base, e))?;
+        self.base_locale = RoseyLocale::from_str(&base)
+            .map_err(|_| RoseyError::parse(&config.base, "expected a Rosey locale file"))?;
+
+        if let Ok(value) = read_to_string(&config.memory) {
+            self.memory =
+                serde_json::from_str(&value).map_err(|e| RoseyError::parse(&config.memory, e))?;
+        }
+
+        reject_po_locales(&config.locales, "suggest")?;
+        let walker = globwalk::GlobWalkerBuilder::from_patterns(&config.locales, &["**/*.json"])
+            .build()
+            .map_err(|e| RoseyError::io(&config.locales, std::io::Error::other(e)))?
+            .filter_map(Result::ok)
+            .collect::<Vec<_>>();
+
+        for file in walker {
+            let locale = file
+                .path()
+                .file_stem()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            if !locale.ends_with(".urls") {
+                self.suggest_locale(file.path(), &locale)?;
+            }
+        }
+
+        let config = &self.options.config;
+        let output = serde_json::to_string_pretty(&self.memory).unwrap();
+        write_output(&config.memory, &output)
+    }
+
+    fn suggest_locale(&mut self, file: &Path, locale: &str) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let content = read_to_string(file).map_err(|e| RoseyError::io(file, e))?;
+        let mut translation = match serde_json::from_str::<RoseyTranslation>(&content) {
+            Ok(translation) if translation.is_empty() => RoseyTranslation::V2(BTreeMap::default()),
+            Ok(translation) => translation,
+            Err(_) => {
+                eprintln!("Failed to parse {file:?}");
+                return Ok(());
+            }
+        };
+        let RoseyTranslation::V2(keys) = &mut translation else {
+            eprintln!("Skipping {file:?}: suggestions need a version 2 locale file");
+            return Ok(());
+        };
+
+        // Remember the current translations before any of them are replaced
+        let memory = self.memory.entry(locale.to_string()).or_default();
+        for entry in keys.values() {
+            let original = entry.previous_original.as_ref().or(entry.original.as_ref());
+            if let (Some(original), Some(value)) = (original, &entry.value) {
+                if !original.is_empty() && !value.is_empty() {
+                    memory.insert(original.clone(), value.clone());
+                }
+            }
+        }
+
+        let memory_lengths = memory_lengths(memory);
+        let threshold = f64::from(config.suggest_threshold) / 100.0;
+        let (mut exact, mut fuzzy) = (0, 0);
+        for (key, base_entry) in self.base_locale.keys.normalize() {
+            let Some(base_original) = base_entry.original else {
+                continue;
+            };
+            let needs_suggestion = keys.get(&key).is_none_or(|entry| {
+                entry.value.as_deref().is_none_or(str::is_empty)
+                    || entry.original.as_ref() != Some(&base_original)
+                    || entry.previous_original.is_some()
+            });
+            if !needs_suggestion {
+                continue;
+            }
+            let Some(suggestion) =
+                best_suggestion(memory, &memory_lengths, &base_original, threshold)
+            else {
+                continue;
+            };
+
+            // Fuzzy suggestions keep the original they were translated from,
+            // so that they are still reported as outdated until reviewed
+            let original = if suggestion.exact {
+                base_original
+            } else {
+                suggestion.original.to_string()
+            };
+            let entry = keys.entry(key).or_insert_with(|| RoseyTranslationEntry {
+                pages: base_entry.pages,
+                total: base_entry.total,
+                ..Default::default()
+            });
+            if entry.original.as_ref() == Some(&original)
+                && entry.value.as_deref() == Some(suggestion.value)
+                && entry.previous_original.is_none()
+            {
+                continue;
+            }
+
+            entry.original = Some(original);
+            entry.previous_original = None;
+            entry.value = Some(suggestion.value.to_string());
+            entry.status = Some("needs-review".to_string());
+            if suggestion.exact {
+                exact += 1;
+            } else {
+                fuzzy += 1;
+            }
+        }
+
+        println!("{locale}: {exact} exact and {fuzzy} fuzzy suggestions");
+        if exact + fuzzy > 0 {
+            let output = serde_json::to_string_pretty(&translation).unwrap();
+            write_output(file, &output)?;
+        }
+        Ok(())
+    }
+}
+
+fn memory_lengths(memory: &BTreeMap<String, String>) -> RoseyMemoryLengths<'_> {
+    let mut lengths = RoseyMemoryLengths::default();
+    for (original, value) in memory {
+        let chars = original.chars().collect::<Vec<_>>();
+        lengths
+            .entry(chars.len())
+            .or_default()
+            .push((original, value, chars));
+    }
+    lengths
+}
+
+fn best_suggestion<'a>(
+    memory: &'a BTreeMap<String, String>,
+    memory_lengths: &RoseyMemoryLengths<'a>,
+    original: &str,
+    threshold: f64,
+) -> Option<RoseySuggestion<'a>> {
+    if let Some((original, value)) = memory.get_key_value(original) {
+        return Some(RoseySuggestion {
+            original,
+            value,
+            exact: true,
+        });
+    }
+
+    // Only originals with a similar length can be within the threshold
+    let original = original.chars().collect::<Vec<_>>();
+    let min_length = (original.len() as f64 * threshold).floor() as usize;
+    let max_length = if threshold > 0.0 {
+        (original.len() as f64 / threshold).ceil() as usize
+    } else {
+        usize::MAX
+    };
+
+    let mut best: Option<(f64, RoseySuggestion)> = None;
+    let candidates = memory_lengths
+        .range(min_length..=max_length)
+        .flat_map(|(_, candidates)| candidates);
+    for (candidate, value, candidate_chars) in candidates {
+        let longest = original.len().max(candidate_chars.len()) as f64;
+        let shortest = original.len().min(candidate_chars.len()) as f64;
+        if longest == 0.0 || shortest / longest < threshold {
+            continue;
+        }
+
+        let similarity = 1.0 - edit_distance(&original, candidate_chars) as f64 / longest;
+        if similarity >= threshold && best.as_ref().is_none_or(|(score, _)| similarity > *score) {
+            best = Some((
+                similarity,
+                RoseySuggestion {
+                    original: candidate,
+                    value,
+                    exact: false,
+                },
+            ));
+        }
+    }
+    best.map(|(_, suggestion)| suggestion)
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
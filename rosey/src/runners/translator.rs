@@ -0,0 +1,175 @@
+mod provider;
+
+use regex::{Captures, Regex};
+use std::{collections::BTreeMap, fs::read_to_string, io::ErrorKind, str::FromStr, sync::LazyLock};
+
+use super::write_output;
+use crate::{RoseyError, RoseyLocale, RoseyOptions, RoseyTranslation, RoseyTranslationEntry};
+use provider::provider_for;
+
+const BATCH_SIZE: usize = 50;
+
+static PROTECTED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<[^>]*>|&#?\w+;|\{\s*\w+\s*\}").unwrap());
+
+pub struct RoseyTranslator {
+    options: RoseyOptions,
+}
+
+impl From<RoseyOptions> for RoseyTranslator {
+    fn from(options: RoseyOptions) -> Self {
+        RoseyTranslator { options }
+    }
+}
+
+impl RoseyTranslator {
+    pub fn run(&mut self) -> Result<(), RoseyError> {
+        let config = &self.options.config;
+        let (Some(locale), Some(provider_name)) = (&config.translate_locale, &config.provider)
+        else {
+            return Err(RoseyError::InvalidConfig(
+                "rosey translate needs a locale and a provider, e.g. `--locale de --provider libretranslate`".into(),
+            ));
+        };
+        let provider = provider_for(&config.providers[provider_name]);
+
+        let base = read_to_string(&config.base).map_err(|e| RoseyError::io(&config.base, e))?;
+        let base_locale = RoseyLocale::from_str(&base)
+            .map_err(|_| RoseyError::parse(&config.base, "expected a Rosey locale file"))?;
+
+        let file = config.locales.join(format!("{locale}.json"));
+        let mut translation = match read_to_string(&file) {
+            Ok(content) => serde_json::from_str::<RoseyTranslation>(&content)
+                .map_err(|e| RoseyError::parse(&file, e))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => RoseyTranslation::V2(BTreeMap::default()),
+            Err(e) => return Err(RoseyError::io(&file, e)),
+        };
+        if translation.is_empty() {
+            translation = RoseyTranslation::V2(BTreeMap::default());
+        }
+        let RoseyTranslation::V2(keys) = &mut translation else {
+            return Err(RoseyError::parse(
+                &file,
+                "machine translations need a version 2 locale file",
+            ));
+        };
+
+        // Keys without a translation, with the HTML in their original protected
+        let mut pending = vec![];
+        let mut skipped = 0;
+        for (key, base_entry) in base_locale.keys.normalize() {
+            let Some(original) = base_entry.original.filter(|o| !o.trim().is_empty()) else {
+                continue;
+            };
+            let translated = keys
+                .get(&key)
+                .is_some_and(|entry| entry.value.as_deref().is_some_and(|v| !v.is_empty()));
+            if translated || base_entry.plurals.is_some() {
+                continue;
+            }
+            let (text, protected) = protect(&original);
+            if text.contains(['{', '}']) {
+                eprintln!("Skipping \"{key}\": messages with plural or select placeholders can't be machine translated");
+                skipped += 1;
+                continue;
+            }
+            pending.push((
+                key,
+                original,
+                text,
+                protected,
+                base_entry.pages,
+                base_entry.total,
+            ));
+        }
+
+        // The locale file is written after each batch, so a failing provider
+        // doesn't lose the translations it has already returned
+        let mut translated = 0;
+        for batch in pending.chunks(BATCH_SIZE) {
+            let batch_start = translated;
+            let texts = batch
+                .iter()
+                .map(|(_, _, text, ..)| text.clone())
+                .collect::<Vec<_>>();
+            let values = provider
+                .translate(&texts, &config.default_language, locale)
+                .map_err(|e| RoseyError::provider(provider_name, e))?;
+            if values.len() != texts.len() {
+                return Err(RoseyError::provider(
+                    provider_name,
+                    format!("sent {} strings, got {} back", texts.len(), values.len()),
+                ));
+            }
+
+            for ((key, original, _, protected, pages, total), value) in batch.iter().zip(values) {
+                let Some(value) = restore(&value, protected) else {
+                    eprintln!("Skipping \"{key}\": the translation didn't keep the HTML and placeholders of the original");
+                    skipped += 1;
+                    continue;
+                };
+                let entry = keys
+                    .entry(key.clone())
+                    .or_insert_with(|| RoseyTranslationEntry {
+                        pages: pages.clone(),
+                        total: *total,
+                        ..Default::default()
+                    });
+                entry.original = Some(original.clone());
+                entry.previous_original = None;
+                entry.value = Some(value);
+                entry.status = Some("draft".into());
+                entry.translator = Some(provider_name.clone());
+                entry.machine_translated = true;
+                translated += 1;
+            }
+
+            if translated > batch_start {
+                let output =
+                    serde_json::to_string_pretty(keys).map_err(|e| RoseyError::parse(&file, e))?;
+                write_output(&file, &output)?;
+            }
+        }
+
+        if translated == 0 && skipped > 0 {
+            return Err(RoseyError::provider(
+                provider_name,
+                format!("none of the missing {locale} translations could be machine translated, {skipped} skipped"),
+            ));
+        }
+
+        println!(
+            "{locale}: {translated} machine translations from {provider_name}, {skipped} skipped"
+        );
+        Ok(())
+    }
+}
+
+fn token(index: usize) -> String {
+    format!("⟦{index}⟧")
+}
+
+// HTML tags, entities and simple message placeholders are swapped for numbered
+// tokens that translation services leave alone, and put back afterwards
+fn protect(text: &str) -> (String, Vec<String>) {
+    let mut protected = vec![];
+    let text = PROTECTED
+        .replace_all(text, |captures: &Captures| {
+            protected.push(captures[0].to_string());
+            token(protected.len() - 1)
+        })
+        .into_owned();
+    (text, protected)
+}
+
+fn restore(text: &str, protected: &[String]) -> Option<String> {
+    let mut text = text.to_string();
+    for (index, original) in protected.iter().enumerate() {
+        let token = token(index);
+        if text.matches(&token).count() != 1 {
+            return None;
+        }
+        text = text.replacen(&token, original, 1);
+    }
+    Some(text)
+}
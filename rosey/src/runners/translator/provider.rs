@@ -0,0 +1,144 @@
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+
+use crate::RoseyProviderConfig;
+
+// How long a single batch request to an HTTP provider may take
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub trait RoseyTranslationProvider {
+    fn translate(
+        &self,
+        texts: &[String],
+        source: &str,
+        target: &str,
+    ) -> Result<Vec<String>, String>;
+}
+
+pub fn provider_for(config: &RoseyProviderConfig) -> Box<dyn RoseyTranslationProvider + '_> {
+    match config {
+        RoseyProviderConfig::Http {
+            url,
+            api_key,
+            headers,
+        } => Box::new(HttpProvider {
+            agent: ureq::Agent::config_builder()
+                .timeout_global(Some(HTTP_TIMEOUT))
+                .build()
+                .into(),
+            url,
+            api_key,
+            headers,
+        }),
+        RoseyProviderConfig::Command { command, args } => {
+            Box::new(CommandProvider { command, args })
+        }
+    }
+}
+
+// Posts a LibreTranslate style request, e.g. `{ "q": [...], "source": "en", "target": "de" }`,
+// and reads the translations from the `translatedText` of the response
+struct HttpProvider<'a> {
+    agent: ureq::Agent,
+    url: &'a str,
+    api_key: &'a Option<String>,
+    headers: &'a BTreeMap<String, String>,
+}
+
+impl RoseyTranslationProvider for HttpProvider<'_> {
+    fn translate(
+        &self,
+        texts: &[String],
+        source: &str,
+        target: &str,
+    ) -> Result<Vec<String>, String> {
+        let mut body = json!({
+            "q": texts,
+            "source": source,
+            "target": target,
+            "format": "text",
+        });
+        if let Some(api_key) = self.api_key {
+            body["api_key"] = Value::String(api_key.clone());
+        }
+
+        let mut request = self.agent.post(self.url);
+        for (name, value) in self.headers {
+            request = request.header(name, value);
+        }
+        let mut response = request.send_json(&body).map_err(|e| e.to_string())?;
+        let response: Value = response.body_mut().read_json().map_err(|e| e.to_string())?;
+
+        match response.get("translatedText") {
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(|value| value.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| format!("expected strings in translatedText, got {response}")),
+            Some(Value::String(value)) if texts.len() == 1 => Ok(vec![value.clone()]),
+            _ => Err(format!(
+                "expected a translatedText array in the response, got {response}"
+            )),
+        }
+    }
+}
+
+// Runs the command once for each string, writing the string to its stdin and
+// reading the translation from its stdout
+struct CommandProvider<'a> {
+    command: &'a str,
+    args: &'a [String],
+}
+
+impl RoseyTranslationProvider for CommandProvider<'_> {
+    fn translate(
+        &self,
+        texts: &[String],
+        source: &str,
+        target: &str,
+    ) -> Result<Vec<String>, String> {
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.replace("{source}", source).replace("{target}", target))
+            .collect::<Vec<_>>();
+
+        texts
+            .iter()
+            .map(|text| {
+                let mut child = Command::new(self.command)
+                    .args(&args)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()
+                    .map_err(|e| format!("couldn't run {}: {e}", self.command))?;
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin
+                        .write_all(text.as_bytes())
+                        .map_err(|e| format!("couldn't write to {}: {e}", self.command))?;
+                }
+
+                let output = child
+                    .wait_with_output()
+                    .map_err(|e| format!("couldn't read from {}: {e}", self.command))?;
+                if !output.status.success() {
+                    return Err(format!(
+                        "{} exited with {}: {}",
+                        self.command,
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ));
+                }
+                let translated = String::from_utf8_lossy(&output.stdout);
+                Ok(translated.trim_end_matches(['\n', '\r']).to_string())
+            })
+            .collect()
+    }
+}
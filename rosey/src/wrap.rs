@@ -0,0 +1,168 @@
+use std::fmt::Write;
+
+use charabia::Segment;
+use icu_locale::{LanguageIdentifier, LocaleExpander};
+
+#[derive(PartialEq, Clone, Copy)]
+enum WrapScript {
+    Hangul,
+    Lao,
+    Myanmar,
+    Other,
+}
+
+fn wrap_script(c: char) -> Option<WrapScript> {
+    match c as u32 {
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Some(WrapScript::Hangul),
+        0x0E80..=0x0EFF => Some(WrapScript::Lao),
+        0x1000..=0x109F | 0xA9E0..=0xA9FF | 0xAA60..=0xAA7F => Some(WrapScript::Myanmar),
+        _ if c.is_alphabetic() => Some(WrapScript::Other),
+        _ => None,
+    }
+}
+
+pub fn locale_script(locale: &str) -> Option<String> {
+    let mut locale = locale
+        .replace('_', "-")
+        .parse::<LanguageIdentifier>()
+        .ok()?;
+    LocaleExpander::new_extended().maximize(&mut locale);
+    locale.script.map(|script| script.to_string())
+}
+
+// A wrap entry can be an exact locale, a language, or a script such as `Khmr`
+pub fn wraps_locale(locale: &str, wrap: &[String]) -> bool {
+    let language = locale.split(['-', '_']).next().unwrap_or(locale);
+    let script = locale_script(locale);
+    wrap.iter().any(|entry| {
+        entry.eq_ignore_ascii_case(locale)
+            || entry.eq_ignore_ascii_case(language)
+            || script
+                .as_ref()
+                .is_some_and(|script| entry.eq_ignore_ascii_case(script))
+    })
+}
+
+// Splits text into the words that should be kept together on one line.
+// Korean is written with spaces, so each space separated word is kept whole,
+// and Lao and Burmese are split into syllables, which charabia can't segment.
+pub fn word_segments(text: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    let mut run_start = 0;
+    let mut run_script = None;
+    for (i, c) in text.char_indices() {
+        let Some(script) = wrap_script(c) else {
+            continue;
+        };
+        if run_script.is_some_and(|run_script| run_script != script) {
+            segment_run(&text[run_start..i], run_script, &mut segments);
+            run_start = i;
+        }
+        run_script = Some(script);
+    }
+    segment_run(&text[run_start..], run_script, &mut segments);
+    segments
+}
+
+fn segment_run<'a>(run: &'a str, script: Option<WrapScript>, segments: &mut Vec<&'a str>) {
+    if run.is_empty() {
+        return;
+    }
+    let syllable_break: fn(&[char], usize) -> bool = match script {
+        Some(WrapScript::Hangul) => |_, _| false,
+        Some(WrapScript::Lao) => lao_syllable_break,
+        Some(WrapScript::Myanmar) => myanmar_syllable_break,
+        _ => {
+            segments.extend(run.segment_str());
+            return;
+        }
+    };
+
+    let chars = run.chars().collect::<Vec<_>>();
+    let mut start = 0;
+    let mut offset = 0;
+    for (i, c) in chars.iter().enumerate() {
+        let whitespace_change = i > 0 && c.is_whitespace() != chars[i - 1].is_whitespace();
+        if i > 0 && (whitespace_change || syllable_break(&chars, i)) {
+            segments.push(&run[start..offset]);
+            start = offset;
+        }
+        offset += c.len_utf8();
+    }
+    segments.push(&run[start..]);
+}
+
+fn is_lao_consonant(c: char) -> bool {
+    matches!(c, '\u{0E81}'..='\u{0EAE}' | '\u{0EDC}'..='\u{0EDF}')
+}
+
+fn is_lao_preposed_vowel(c: char) -> bool {
+    matches!(c, '\u{0EC0}'..='\u{0EC4}')
+}
+
+fn is_lao_vowel_sign(c: char) -> bool {
+    matches!(c, '\u{0EB0}'..='\u{0EBC}' | '\u{0EC8}'..='\u{0ECE}')
+}
+
+// Breaks before a vowel written ahead of its consonant, or before a consonant
+// carrying a vowel, unless it follows another consonant that starts a cluster
+fn lao_syllable_break(chars: &[char], i: usize) -> bool {
+    let (previous, c) = (chars[i - 1], chars[i]);
+    if is_lao_preposed_vowel(c) {
+        return !previous.is_whitespace();
+    }
+    if !is_lao_consonant(c) || is_lao_preposed_vowel(previous) {
+        return false;
+    }
+    if c == '\u{0EA7}' && is_lao_consonant(previous) {
+        return false;
+    }
+    chars
+        .get(i + 1)
+        .is_some_and(|&next| is_lao_vowel_sign(next))
+        && !previous.is_whitespace()
+        && !chars[..i]
+            .iter()
+            .rev()
+            .take_while(|&&c| is_lao_consonant(c) || is_lao_preposed_vowel(c))
+            .any(|&c| is_lao_preposed_vowel(c))
+}
+
+// Breaks before each consonant or independent vowel that isn't stacked under
+// the previous consonant or killed by an asat
+fn myanmar_syllable_break(chars: &[char], i: usize) -> bool {
+    let (previous, c) = (chars[i - 1], chars[i]);
+    let starts_syllable =
+        matches!(c, '\u{1000}'..='\u{102A}' | '\u{103F}' | '\u{104C}'..='\u{104F}');
+    let mut following = chars[i + 1..].iter().skip_while(|&&c| c == '\u{1037}');
+    starts_syllable
+        && previous != '\u{1039}'
+        && !previous.is_whitespace()
+        && !matches!(following.next(), Some('\u{1039}' | '\u{103A}'))
+}
+
+// Keeps words together with a nowrap span around each word, or marks the
+// breaks between words with `<wbr>` or a zero-width space
+pub fn wrap_words(text: &str, mode: &str, class: &Option<String>) -> String {
+    let segments = word_segments(text);
+    let mut output = String::new();
+    for (i, segment) in segments.iter().enumerate() {
+        if segment.trim().is_empty() {
+            output.push_str(segment);
+            continue;
+        }
+        let follows_word = i > 0 && !segments[i - 1].trim().is_empty();
+        match (mode, class) {
+            ("wbr", _) if follows_word => write!(output, "<wbr>{segment}"),
+            ("zwsp", _) if follows_word => write!(output, "\u{200B}{segment}"),
+            ("wbr" | "zwsp", _) => write!(output, "{segment}"),
+            (_, Some(class)) => write!(output, "<span class=\"{class}\">{segment}</span>"),
+            (_, None) => write!(
+                output,
+                "<span style=\"white-space: nowrap;\">{segment}</span>"
+            ),
+        }
+        .expect("Failed to wrap words");
+    }
+    output
+}